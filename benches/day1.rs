@@ -5,9 +5,11 @@ static EXAMPLE: [i32; 6] = [1721, 979, 366, 299, 675, 1456];
 
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("nested find", |b| b.iter(|| 
-        days::day1::find2(&EXAMPLE.to_vec(), black_box(2020))));
+        days::day1::find2(&EXAMPLE, black_box(2020))));
     c.bench_function("itertools find", |b| b.iter(||
-        days::day1::find(&EXAMPLE.to_vec(), black_box(2020), black_box(3))));
+        days::day1::find(&EXAMPLE, black_box(2020), black_box(3))));
+    c.bench_function("k_sum find", |b| b.iter(||
+        days::day1::k_sum(&EXAMPLE, black_box(2020), black_box(3))));
 }
 
 criterion_group!(benches, criterion_benchmark);
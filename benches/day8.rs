@@ -0,0 +1,16 @@
+use std::fs;
+use criterion::{criterion_group, criterion_main, Criterion};
+use aoc20::days::day8::{self, Instruction, Program};
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let contents = fs::read_to_string("data/day8.txt").expect("Missing data/day8.txt");
+    let code: Vec<Instruction> = contents.lines().map(|l| Instruction::parse(l).unwrap()).collect();
+    let program = Program::new(code);
+    c.bench_function("brute force repair", |b| b.iter(||
+        day8::part2_brute_force(program.clone())));
+    c.bench_function("reachability repair", |b| b.iter(||
+        day8::part2(program.clone())));
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,26 @@
+use std::fs;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use aoc20::days;
+
+// One group per registered day: parsing, part 1 and part 2 on the data/ input.
+// Filter with e.g. `cargo bench --bench days -- day17/`.
+fn days_benchmark(c: &mut Criterion) {
+    for day in days::all() {
+        let contents = match fs::read_to_string(day.data_file()) {
+            Ok(contents) => contents,
+            Err(_) => continue,
+        };
+        let solution = day.parse(&contents).expect("Failed to parse input");
+
+        let mut group = c.benchmark_group(day.name());
+        // several days take seconds per part, keep the sample count down
+        group.sample_size(10);
+        group.bench_function("parse", |b| b.iter(|| day.parse(black_box(&contents))));
+        group.bench_function("part1", |b| b.iter(|| solution.part1()));
+        group.bench_function("part2", |b| b.iter(|| solution.part2()));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, days_benchmark);
+criterion_main!(benches);
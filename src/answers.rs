@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use crate::runner::{DayReport, Status};
+
+/// Recorded puzzle answers keyed by day and part.
+///
+/// The file is a small subset of TOML, one table per day:
+///
+/// ```text
+/// [day1]
+/// part1 = 996996
+/// part2 = "9210402"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: HashMap<(u8, u8), String>,
+}
+
+impl Answers {
+
+    pub fn parse(contents: &str) -> Result<Answers, String> {
+        let mut answers = HashMap::new();
+        let mut day: Option<u8> = None;
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let err = |msg: &str| format!("line {}: {}", i + 1, msg);
+            if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let number = table.trim().strip_prefix("day").and_then(|n| n.parse::<u8>().ok());
+                day = Some(number.ok_or_else(|| err("expected a `[dayN]` table"))?);
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| err("expected `partN = answer`"))?;
+            let part = key.trim().strip_prefix("part")
+                          .and_then(|p| p.parse::<u8>().ok())
+                          .ok_or_else(|| err("expected a `partN` key"))?;
+            let value = value.trim();
+            let value = match value.strip_prefix('"') {
+                Some(v) => v.strip_suffix('"').ok_or_else(|| err("unterminated string"))?,
+                None => value,
+            };
+            let day = day.ok_or_else(|| err("answer outside of a `[dayN]` table"))?;
+            answers.insert((day, part), value.to_string());
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn load(filename: &str) -> Result<Answers, String> {
+        let contents = fs::read_to_string(filename).map_err(|e| format!("{}: {}", filename, e))?;
+        Answers::parse(&contents).map_err(|e| format!("{}: {}", filename, e))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|a| a.as_str())
+    }
+}
+
+/// Result of checking one part against the recorded answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Match,
+    Mismatch { expected: String, actual: String },
+    Missing,
+    Error(String),
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Mismatch { .. } | Verdict::Error(_))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Match => write!(f, "match"),
+            Verdict::Mismatch { expected, actual } => write!(f, "MISMATCH expected {} got {}", expected, actual),
+            Verdict::Missing => write!(f, "missing"),
+            Verdict::Error(e) => write!(f, "ERROR {}", e),
+        }
+    }
+}
+
+/// Check both parts of a day's report against the recorded answers.
+pub fn verify(report: &DayReport, answers: &Answers) -> Vec<(u8, Verdict)> {
+    (1..=2).map(|part| {
+        let expected = answers.get(report.day, part);
+        let verdict = match &report.status {
+            Status::Solved(parts) => match (parts.iter().find(|p| p.part == part), expected) {
+                (Some(p), Some(expected)) => match &p.answer {
+                    Ok(a) if a.to_string() == expected => Verdict::Match,
+                    Ok(a) => Verdict::Mismatch { expected: expected.to_string(), actual: a.to_string() },
+                    Err(e) => Verdict::Error(format!("panicked: {}", e)),
+                },
+                _ => Verdict::Missing,
+            },
+            Status::MissingInput => Verdict::Missing,
+            Status::Failed(e) => Verdict::Error(e.clone()),
+        };
+        (part, verdict)
+    }).collect()
+}
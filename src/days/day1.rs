@@ -1,29 +1,30 @@
-use std::fs;
+use std::collections::HashMap;
 use itertools::Itertools;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
-pub fn day1(args: &[String]) -> i32 {
-    println!("Day 1");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
+#[derive(Debug, Clone)]
+pub struct Day1 {
+    entries: Vec<i32>,
+}
 
-    let entries: Vec<i32> = contents.lines().map(|l| l.parse::<i32>().unwrap()).collect();
+impl Solution for Day1 {
 
-    println!("Part 1: {:}", find1(&entries, 2020).unwrap());
-    println!("Part 2: {:}", find2(&entries, 2020).unwrap());
+    fn parse(contents: &str) -> Result<Day1, AocError> {
+        let entries = error::parse_lines(contents, error::parse_number)?;
+        Ok(Day1 { entries })
+    }
 
-    println!("Part 1: {:}", find(&entries, 2020, 2).unwrap());
-    println!("Part 2: {:}", find(&entries, 2020, 3).unwrap());
+    fn part1(&self) -> Answer {
+        k_product(&self.entries, 2020, 2).into()
+    }
 
-    0
+    fn part2(&self) -> Answer {
+        k_product(&self.entries, 2020, 3).into()
+    }
 }
 
-pub fn find1(entries: &Vec<i32>, sum: i32) -> Option<i32> {
+pub fn find1(entries: &[i32], sum: i32) -> Option<i32> {
     let mut r = None;
     for i in 0..entries.len()-1 {
         for j in i+1..entries.len() {
@@ -35,7 +36,7 @@ pub fn find1(entries: &Vec<i32>, sum: i32) -> Option<i32> {
     r
 }
 
-pub fn find2(entries: &Vec<i32>, sum: i32) -> Option<i32> {
+pub fn find2(entries: &[i32], sum: i32) -> Option<i32> {
     let mut r = None;
     for i in 0..entries.len()-2 {
         for j in i+1..entries.len()-1 {
@@ -49,7 +50,7 @@ pub fn find2(entries: &Vec<i32>, sum: i32) -> Option<i32> {
     r
 }
 
-pub fn find(entries: &Vec<i32>, sum: i32, count: usize) -> Option<i32> {
+pub fn find(entries: &[i32], sum: i32, count: usize) -> Option<i32> {
     let mut r = None;
     entries.iter().cloned().combinations(count).for_each(|c| {
         if c.iter().sum::<i32>() == sum {
@@ -57,4 +58,130 @@ pub fn find(entries: &Vec<i32>, sum: i32, count: usize) -> Option<i32> {
         }
     });
     r
-}
\ No newline at end of file
+}
+
+/// What `k_sum_with` reports for each matching tuple.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Output {
+    Indices,
+    Values,
+    Sum,
+    Product,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KSum {
+    Indices(Vec<usize>),
+    Values(Vec<i32>),
+    Sum(i64),
+    Product(i64),
+}
+
+// All distinct sets of `k` entries adding up to `target`, as ascending index
+// tuples in ascending order. Entries with equal values at different indices
+// count as different sets.
+pub fn k_sum(entries: &[i32], target: i64, k: usize) -> Vec<Vec<usize>> {
+    let mut tuples = match k {
+        0 if target == 0 => vec![vec![]],
+        0 => vec![],
+        1 => entries.iter().enumerate().filter(|(_i, &e)| e as i64 == target).map(|(i, _e)| vec![i]).collect(),
+        2 => two_sum(entries, target),
+        3 => three_sum(entries, target),
+        _ => meet_in_the_middle(entries, target, k),
+    };
+    tuples.iter_mut().for_each(|t| t.sort_unstable());
+    tuples.sort_unstable();
+    tuples
+}
+
+pub fn k_sum_with(entries: &[i32], target: i64, k: usize, output: Output) -> Vec<KSum> {
+    k_sum(entries, target, k).into_iter().map(|t| {
+        let values = t.iter().map(|&i| entries[i]);
+        match output {
+            Output::Indices => KSum::Indices(t),
+            Output::Values => KSum::Values(values.collect()),
+            Output::Sum => KSum::Sum(values.map(|v| v as i64).sum()),
+            Output::Product => KSum::Product(values.map(|v| v as i64).product()),
+        }
+    }).collect()
+}
+
+// Product of the first set of `k` entries adding up to `target`
+pub fn k_product(entries: &[i32], target: i64, k: usize) -> Option<i64> {
+    match k_sum_with(entries, target, k, Output::Product).into_iter().next() {
+        Some(KSum::Product(p)) => Some(p),
+        _ => None,
+    }
+}
+
+// O(n + matches): look each entry's complement up among the entries before it
+fn two_sum(entries: &[i32], target: i64) -> Vec<Vec<usize>> {
+    let mut seen: HashMap<i64, Vec<usize>> = HashMap::new();
+    let mut tuples = vec![];
+    for (j, &e) in entries.iter().enumerate() {
+        if let Some(is) = seen.get(&(target - e as i64)) {
+            tuples.extend(is.iter().map(|&i| vec![i, j]));
+        }
+        seen.entry(e as i64).or_default().push(j);
+    }
+    tuples
+}
+
+// O(n^2 + matches): sort, then for each first entry close in on the other two
+// from both ends of the rest
+fn three_sum(entries: &[i32], target: i64) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..entries.len()).collect();
+    order.sort_unstable_by_key(|&i| entries[i]);
+    let value = |p: usize| entries[order[p]] as i64;
+    let mut tuples = vec![];
+    for a in 0..order.len() {
+        let (mut lo, mut hi) = (a + 1, order.len().saturating_sub(1));
+        while lo < hi {
+            let sum = value(a) + value(lo) + value(hi);
+            if sum < target {
+                lo += 1;
+            } else if sum > target {
+                hi -= 1;
+            } else if value(lo) == value(hi) {
+                // everything from lo to hi is the same value, any pair of them works
+                for (l, h) in (lo..=hi).tuple_combinations() {
+                    tuples.push(vec![order[a], order[l], order[h]]);
+                }
+                break;
+            } else {
+                // pair every copy of the low value with every copy of the high value
+                let lo_end = (lo..hi).find(|&p| value(p) != value(lo)).unwrap_or(hi);
+                let hi_start = (lo_end..=hi).find(|&p| value(p) == value(hi)).unwrap_or(hi);
+                for l in lo..lo_end {
+                    for h in hi_start..=hi {
+                        tuples.push(vec![order[a], order[l], order[h]]);
+                    }
+                }
+                lo = lo_end;
+                hi = hi_start - 1;
+            }
+        }
+    }
+    tuples
+}
+
+// O(n^ceil(k/2) + matches): index the sums of every combination of the first
+// half, then look up the complement of each combination of the second half.
+// A set is only counted when its lowest k/2 indices make up the first half.
+fn meet_in_the_middle(entries: &[i32], target: i64, k: usize) -> Vec<Vec<usize>> {
+    let (k1, k2) = (k / 2, k - k / 2);
+    let sum = |c: &Vec<usize>| c.iter().map(|&i| entries[i] as i64).sum::<i64>();
+    let mut halves: HashMap<i64, Vec<Vec<usize>>> = HashMap::new();
+    for c in (0..entries.len()).combinations(k1) {
+        halves.entry(sum(&c)).or_default().push(c);
+    }
+    let mut tuples = vec![];
+    for c in (0..entries.len()).combinations(k2) {
+        if let Some(firsts) = halves.get(&(target - sum(&c))) {
+            for first in firsts.iter().filter(|f| f.last() < c.first()) {
+                tuples.push(first.iter().chain(c.iter()).copied().collect());
+            }
+        }
+    }
+    tuples
+}
@@ -1,27 +1,31 @@
-use std::fs;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
-pub fn day10(args: &[String]) -> i32 {
-    println!("Day 10");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
+#[derive(Debug, Clone)]
+pub struct Day10 {
+    ratings: Vec<usize>,
+}
+
+impl Solution for Day10 {
+
+    fn parse(contents: &str) -> Result<Day10, AocError> {
+        Ok(Day10 { ratings: error::parse_lines(contents, error::parse_number)? })
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
 
-    let ratings: Vec<usize> = contents.lines().map(|l| l.parse::<usize>().unwrap()).collect();
+    fn part1(&self) -> Answer {
+        part1(&self.ratings).map(|p1| p1.0 * p1.2).into()
+    }
 
-    let p1 = part1(&ratings);
-    println!("{:?}", p1);
-    println!("Part 1: {:?}", p1.0 * p1.2);
-    0
+    fn part2(&self) -> Answer {
+        part2(&self.ratings).into()
+    }
 }
 
-pub fn part1(ratings: &Vec<usize>) -> (usize, usize, usize) {
-    let mut sorted = ratings.clone();
-    sorted.sort();
+// Count the 1, 2 and 3 jolt differences along the chain of every adapter.
+// None if two adapters can't be connected, including duplicate ratings.
+pub fn part1(ratings: &[usize]) -> Option<(usize, usize, usize)> {
+    let mut sorted = ratings.to_vec();
+    sorted.sort_unstable();
     // built-in adapter is always 3 higher than the highest adapter, so
     // seed the 3 difference by 1 
     let mut counts = ( 0, 0, 1 );
@@ -31,9 +35,34 @@ pub fn part1(ratings: &Vec<usize>) -> (usize, usize, usize) {
             3 => counts.2 += 1,
             2 => counts.1 += 1,
             1 => counts.0 += 1,
-            _ => assert!(false, "Invalid difference")
+            _ => return None,
         }
         jolts = r;
     }
-    counts
-}
\ No newline at end of file
+    Some(counts)
+}
+
+// Count the distinct adapter chains from the outlet (0 jolts) to the device
+// (3 higher than the highest adapter)
+pub fn part2(ratings: &[usize]) -> u64 {
+    let mut sorted = ratings.to_vec();
+    sorted.sort_unstable();
+    // ways[i] is the number of chains from the outlet ending at adapter i
+    let mut ways = vec![0u64; sorted.len()];
+    for i in 0..sorted.len() {
+        if sorted[i] <= 3 {
+            ways[i] = 1;
+        }
+        for j in (0..i).rev() {
+            if sorted[i] - sorted[j] > 3 {
+                break;
+            }
+            ways[i] += ways[j];
+        }
+    }
+    // only the highest rated adapters reach the device
+    match sorted.last() {
+        Some(&max) => sorted.iter().zip(ways).filter(|(&r, _)| r == max).map(|(_, w)| w).sum(),
+        None => 1,
+    }
+}
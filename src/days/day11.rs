@@ -1,6 +1,7 @@
-use std::fs;
 use std::fmt;
 use itertools::iproduct;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum State {
@@ -16,12 +17,12 @@ pub enum Part {
 }
 
 impl State {
-    pub fn parse(c: char) -> State{
+    pub fn parse(c: char) -> Result<State, AocError> {
         match c {
-            'L' => State::Open,
-            '#' => State::Occupied,
-            '.' => State::Floor,
-            _ => unreachable!("Invalid option"),
+            'L' => Ok(State::Open),
+            '#' => Ok(State::Occupied),
+            '.' => Ok(State::Floor),
+            _ => Err(AocError::parse(format!("expected `L`, `#` or `.`, found `{}`", c))),
         }
     }
 }
@@ -46,15 +47,24 @@ pub struct SeatLayout {
 
 impl SeatLayout {
 
-    pub fn new(input: &String, part: Part) -> SeatLayout {
-        let map: Vec<Vec<State>> = input.lines()
-                    .map(|l| l.chars().map(|c| State::parse(c)).collect())
-                    .collect();
+    pub fn new(input: &str, part: Part) -> Result<SeatLayout, AocError> {
+        let mut width = None;
+        let map: Vec<Vec<State>> = error::parse_lines(input, |l| {
+            let row = l.chars()
+                       .enumerate()
+                       .map(|(i, c)| State::parse(c).map_err(|e| e.at_column(i + 1)))
+                       .collect::<Result<Vec<State>, AocError>>()?;
+            error::same_width(&mut width, row.len())?;
+            Ok(row)
+        })?;
+        if map.is_empty() {
+            return Err(AocError::parse("empty seat layout"));
+        }
         let (max_magnitude, min_occupied_count) = match part {
             Part::Part1 => (1, 4),
             Part::Part2 => (10_000, 5),
         };
-        SeatLayout { map, max_magnitude, min_occupied_count }
+        Ok(SeatLayout { map, max_magnitude, min_occupied_count })
     }
 
     pub fn width(&self) -> usize {
@@ -112,7 +122,7 @@ impl SeatLayout {
                .count()
     }
 
-    pub fn state_count(positions: &Vec<Option<State>>, state: State) -> usize {
+    pub fn state_count(positions: &[Option<State>], state: State) -> usize {
         positions.iter().filter(|&p| *p == Some(state))
                  .count()
     }
@@ -176,33 +186,32 @@ impl fmt::Display for SeatLayout {
 }
 
 
-pub fn day11(args: &[String]) -> i32 {
-    println!("Day 11");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
+// run rounds until the layout stops changing, returning the occupied count
+fn occupied_when_stable(mut layout: SeatLayout) -> usize {
+    while layout.run_round() != 0 {}
+    layout.count(State::Occupied)
+}
+
+#[derive(Debug, Clone)]
+pub struct Day11 {
+    part1: SeatLayout,
+    part2: SeatLayout,
+}
+
+impl Solution for Day11 {
+
+    fn parse(contents: &str) -> Result<Day11, AocError> {
+        Ok(Day11 {
+            part1: SeatLayout::new(contents, Part::Part1)?,
+            part2: SeatLayout::new(contents, Part::Part2)?,
+        })
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
 
-    let mut layout = SeatLayout::new(&contents, Part::Part1);
-    loop {
-        //println!("{}", layout);
-        if layout.run_round() == 0 {
-            break;
-        }
+    fn part1(&self) -> Answer {
+        occupied_when_stable(self.part1.clone()).into()
     }
-    println!("Part 1: {}", layout.count(State::Occupied));
 
-    layout = SeatLayout::new(&contents, Part::Part2);
-    loop {
-        //println!("{}", layout);
-        if layout.run_round() == 0 {
-            break;
-        }
+    fn part2(&self) -> Answer {
+        occupied_when_stable(self.part2.clone()).into()
     }
-    println!("Part 2: {}", layout.count(State::Occupied));
-    0
 }
@@ -1,5 +1,6 @@
-use std::fs;
 use regex::Regex;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Direction {
@@ -39,6 +40,12 @@ pub struct Ship {
     facing: Direction
 }
 
+impl Default for Ship {
+    fn default() -> Ship {
+        Ship::new()
+    }
+}
+
 impl Ship {
 
     // New ship at the origin, facing east, waypoint at 10 east 1 north
@@ -51,11 +58,11 @@ impl Ship {
     }
 
     pub fn turn_waypoint(&mut self, degrees: usize) {
-        match degrees {
+        match degrees % 360 {
             0 => (),
-            90 => self.waypoint = (self.waypoint.1, -1 * self.waypoint.0),
-            180 => self.waypoint = (-1 * self.waypoint.0, -1 * self.waypoint.1),
-            270 => self.waypoint = (-1 * self.waypoint.1, self.waypoint.0),
+            90 => self.waypoint = (self.waypoint.1, -self.waypoint.0),
+            180 => self.waypoint = (-self.waypoint.0, -self.waypoint.1),
+            270 => self.waypoint = (-self.waypoint.1, self.waypoint.0),
             _ => panic!("Unknown degree: {}", degrees),
         }
 
@@ -110,36 +117,41 @@ impl Instruction {
         Instruction { action, value }
     }
 
-    pub fn parse(line: &str) -> Instruction {
+    pub fn parse(line: &str) -> Result<Instruction, AocError> {
         lazy_static! {
-            static ref RE: Regex = Regex::new(r"([NESWLRF])(\d+)").unwrap();
+            static ref RE: Regex = Regex::new(r"^([NESWLRF])(\d+)$").unwrap();
         }
-        let caps = RE.captures(line).unwrap();
-        let action = caps.get(1).unwrap().as_str().chars().nth(0).unwrap();
-        let value = caps.get(2).unwrap().as_str().parse::<usize>().unwrap();
-        Instruction::new(action, value)
+        let caps = RE.captures(line)
+                     .ok_or_else(|| AocError::parse("expected an action `N`, `E`, `S`, `W`, `L`, `R` or `F` and a value"))?;
+        let action = caps[1].chars().next().unwrap();
+        let value = caps[2].parse::<usize>()?;
+        if (action == 'L' || action == 'R') && (value % 90 != 0 || value > 360) {
+            return Err(AocError::parse("turns must be a multiple of 90 degrees").at_column(2));
+        }
+        Ok(Instruction::new(action, value))
     }
 }
 
-pub fn day12(args: &[String]) -> i32 {
-    println!("Day 12");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
+#[derive(Debug, Clone)]
+pub struct Day12 {
+    instructions: Vec<Instruction>,
+}
+
+impl Solution for Day12 {
+
+    fn parse(contents: &str) -> Result<Day12, AocError> {
+        Ok(Day12 { instructions: error::parse_lines(contents, Instruction::parse)? })
+    }
+
+    fn part1(&self) -> Answer {
+        let mut ship = Ship::new();
+        self.instructions.iter().for_each(|i| ship.step(i));
+        ship.manhattan_distance().into()
+    }
+
+    fn part2(&self) -> Answer {
+        let mut ship = Ship::new();
+        self.instructions.iter().for_each(|i| ship.step_waypoint(i));
+        ship.manhattan_distance().into()
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
-
-    let instructions: Vec<Instruction> = contents.lines().map(|l| Instruction::parse(l)).collect();
-    let mut ship = Ship::new();
-    instructions.into_iter().for_each(|i| ship.step(&i));
-    println!("Part 1: {:?}", ship.manhattan_distance());
-
-    let instructions: Vec<Instruction> = contents.lines().map(|l| Instruction::parse(l)).collect();
-    let mut ship = Ship::new();
-    instructions.into_iter().for_each(|i| ship.step_waypoint(&i));
-    println!("Part 2: {:?}", ship.manhattan_distance());
-    0
 }
@@ -1,5 +1,6 @@
-use std::fs;
 use crate::days::utils;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Clone)]
 pub struct Schedule {
@@ -9,57 +10,73 @@ pub struct Schedule {
 
 impl Schedule {
 
-    pub fn parse(contents: &str) -> Schedule {
+    pub fn parse(contents: &str) -> Result<Schedule, AocError> {
         let mut lines = contents.lines();
-        let depart = lines.next().unwrap().parse::<i64>().unwrap();
-        let buses = lines.next().unwrap()
-                         .replace("x","1")
-                         .split(",")
-                         .map(|b| b.parse::<i64>().unwrap())
-                         .collect();
-        Schedule { depart, buses }
+        let depart = error::parse_number::<i64>(lines.next().unwrap_or(""))
+                            .map_err(|e| e.at_line(1))?;
+        let line = lines.next().ok_or_else(|| AocError::parse("expected a line of bus IDs").at_line(2))?;
+        // out of service buses ("x") are treated as a bus every minute
+        let mut buses = vec![];
+        let mut column = 1;
+        let mut in_service = false;
+        for b in line.split(',') {
+            let bus = match b {
+                "x" => 1,
+                b => match error::parse_number::<i64>(b) {
+                    Ok(bus) if bus > 0 => { in_service = true; bus },
+                    _ => return Err(AocError::parse(format!("expected a bus ID or `x`, found `{}`", b))
+                                            .at_line(2).at_column(column)),
+                },
+            };
+            buses.push(bus);
+            column += b.len() + 1;
+        }
+        if !in_service {
+            return Err(AocError::parse("expected at least one bus ID").at_line(2));
+        }
+        Ok(Schedule { depart, buses })
     }
 
 }
 
-pub fn calc_part1(schedule: &Schedule) -> i64 {
+pub fn calc_part1(schedule: &Schedule) -> Option<i64> {
     let wait_times: Vec<(i64, i64)> = 
         schedule.buses
             .iter()
             .filter(|&b| *b != 1)
             .map(|&b| (b, (0..).step_by(b as usize)
-                        .skip_while(|&i| i < schedule.depart)
-                        .next().unwrap() - schedule.depart) )
+                        .find(|&i| i >= schedule.depart)
+                        .unwrap() - schedule.depart) )
             .collect();
-    let min_wait = wait_times.iter().min_by_key(|(_b, i)| i).unwrap();
-    min_wait.0 * min_wait.1
+    let min_wait = wait_times.iter().min_by_key(|(_b, i)| i)?;
+    Some(min_wait.0 * min_wait.1)
 }
 
 // Part 2 requires the Chinese Remainder Theorem https://crypto.stanford.edu/pbc/notes/numbertheory/crt.html
-pub fn calc_part2(modulii: &Vec<i64>) -> i64 {
+// None if the bus IDs aren't pairwise coprime
+pub fn calc_part2(modulii: &[i64]) -> Option<i64> {
     let residues: Vec<i64> = (0..modulii.len() as i64).collect();
  
-    let remainder = utils::chinese_remainder(&residues, &modulii).unwrap();
-    modulii.iter().product::<i64>() - remainder
+    let remainder = utils::chinese_remainder(&residues, modulii)?;
+    Some(modulii.iter().product::<i64>() - remainder)
 }
 
-pub fn day13(args: &[String]) -> i32 {
-    println!("Day 13");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
+#[derive(Debug, Clone)]
+pub struct Day13 {
+    schedule: Schedule,
+}
 
-    let schedule = Schedule::parse(&contents);
-    //println!("{:?}", schedule);
+impl Solution for Day13 {
 
-    println!("Part 1: {:?}", calc_part1(&schedule));
+    fn parse(contents: &str) -> Result<Day13, AocError> {
+        Ok(Day13 { schedule: Schedule::parse(contents)? })
+    }
 
-    println!("Part 2: {:?}", calc_part2(&schedule.buses));
+    fn part1(&self) -> Answer {
+        calc_part1(&self.schedule).into()
+    }
 
-    0
+    fn part2(&self) -> Answer {
+        calc_part2(&self.schedule.buses).into()
+    }
 }
@@ -1,6 +1,7 @@
-use std::fs;
 use regex::Regex;
 use std::collections::HashMap;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Mask {
@@ -19,7 +20,7 @@ impl Mask {
         lazy_static! {
             static ref MASK: Regex = Regex::new(r"^mask\s=\s([X01]{36})$").unwrap();
         }
-        if !MASK.is_match(&line) {
+        if !MASK.is_match(line) {
             return None;
         }
         let caps = MASK.captures(line).unwrap();
@@ -48,18 +49,18 @@ impl Mem {
         lazy_static! {
             static ref MEM: Regex = Regex::new(r"^mem\[(\d+)\]\s=\s(\d+)$").unwrap();
         }
-        if !MEM.is_match(&line) {
+        if !MEM.is_match(line) {
             return None;
         }
         let caps = MEM.captures(line).unwrap();
-        let location = caps.get(1).unwrap().as_str().parse::<u64>().unwrap();
-        let value = caps.get(2).unwrap().as_str().parse::<u64>().unwrap();
+        let location = caps.get(1).unwrap().as_str().parse::<u64>().ok()?;
+        let value = caps.get(2).unwrap().as_str().parse::<u64>().ok()?;
         Some(Mem { location, value })
     }
 
 }
 
-pub fn part1(contents: String) -> u64 {
+pub fn part1(contents: &str) -> u64 {
     let mut memory = HashMap::<u64, u64>::new();
     let mut lines = contents.lines(); 
     let mut nextline = lines.next();
@@ -87,7 +88,7 @@ pub fn part1(contents: String) -> u64 {
             //println!("{:?}", mem);
         }
     }
-    memory.iter().map(|(_, v)| v).sum()
+    memory.values().sum()
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -109,7 +110,7 @@ impl MemMask {
         lazy_static! {
             static ref MASK: Regex = Regex::new(r"^mask\s=\s([X01]{36})$").unwrap();
         }
-        if !MASK.is_match(&line) {
+        if !MASK.is_match(line) {
             return None;
         }
         let caps = MASK.captures(line).unwrap();
@@ -159,7 +160,7 @@ impl Iterator for MemMask {
 }
 
 
-pub fn part2(contents: String) -> u64 {
+pub fn part2(contents: &str) -> u64 {
     let mut memory = HashMap::<u64, u64>::new();
     let mut lines = contents.lines(); 
     let mut nextline = lines.next();
@@ -181,32 +182,43 @@ pub fn part2(contents: String) -> u64 {
             if mem.is_none() {
                 break; // is a mask line
             }
-            let mut mk = mask.clone().unwrap();
+            let mk = mask.clone().unwrap();
             let mm = mem.clone().unwrap();
             let initloc = mk.condition(mm.location);
-            while let Some(loc) = mk.next() {
+            for loc in mk {
                 memory.insert(initloc | loc, mm.value);
                 //println!("{:?} {:?}", initloc | loc, mm.value);
             }
         }
     }
-    memory.iter().map(|(_, v)| v).sum()
+    memory.values().sum()
 }
 
-pub fn day14(args: &[String]) -> i32 {
-    println!("Day 14");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
+#[derive(Debug, Clone)]
+pub struct Day14 {
+    contents: String,
+}
 
-    println!("Part 1: {}", part1(contents.clone()));
+impl Solution for Day14 {
 
-    println!("Part 2: {}", part2(contents.clone()));
+    fn parse(contents: &str) -> Result<Day14, AocError> {
+        error::parse_lines(contents, |l| {
+            if Mask::parse(l).is_some() || Mem::parse(l).is_some() {
+                return Ok(());
+            }
+            Err(AocError::parse("expected `mask = <36 of X, 0 or 1>` or `mem[<address>] = <value>`"))
+        })?;
+        if contents.lines().next().and_then(Mask::parse).is_none() {
+            return Err(AocError::parse("program must start with a mask").at_line(1));
+        }
+        Ok(Day14 { contents: contents.to_string() })
+    }
+
+    fn part1(&self) -> Answer {
+        part1(&self.contents).into()
+    }
 
-    0
+    fn part2(&self) -> Answer {
+        part2(&self.contents).into()
+    }
 }
@@ -1,77 +1,94 @@
-use std::fs;
-//use std::collections::HashMap;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Clone)]
 pub struct MemoryGame {
-    turns: Vec::<usize>,
+    // the turn each number was last spoken on, before the latest turn (0 if never)
+    last_seen: Vec<u32>,
+    // the number of turns taken so far and the number spoken on the latest one
+    turn: u32,
+    last: usize,
 }
 
 impl MemoryGame {
 
-    pub fn new(starting: &Vec<usize>) -> MemoryGame {
-        let mut turns = Vec::<usize>::new();
-        starting.iter().for_each(|&s| turns.push(s));
-        MemoryGame { turns }
+    pub fn new(starting: &[usize]) -> MemoryGame {
+        MemoryGame::with_capacity(starting, 0)
+    }
+
+    // Reserve room for a game of `turns` turns; no number spoken can be larger
+    pub fn with_capacity(starting: &[usize], turns: usize) -> MemoryGame {
+        let size = starting.iter().map(|&n| n + 1).chain(Some(turns)).max().unwrap_or(0);
+        let mut game = MemoryGame { last_seen: vec![0; size], turn: 0, last: 0 };
+        for &n in starting {
+            game.speak(n);
+        }
+        game
+    }
+
+    // Record the latest number as seen and move on to `number`
+    fn speak(&mut self, number: usize) {
+        if self.turn > 0 {
+            if self.last >= self.last_seen.len() {
+                self.last_seen.resize((self.last + 1).max(self.last_seen.len() * 2), 0);
+            }
+            self.last_seen[self.last] = self.turn;
+        }
+        self.turn += 1;
+        self.last = number;
     }
 
 }
 
 impl Iterator for MemoryGame {
     type Item = usize;
-    
+
     fn next(&mut self) -> Option<usize> {
-        let number = self.turns.last().unwrap();
-        //println!("{:?}", self.turns);
-        let mut iter = self.turns.iter()
-                    .enumerate()
-                    .rev()
-                    .filter(|(_i, &v)| v == *number)
-                    .take(2);
-        let (last, prev) = (iter.next(), iter.next());
-        //println!("{:?} {:?}", last, prev);
-        let mut turn = 0;
-        if last.is_some() && prev.is_some() {
-            turn = last.unwrap().0 - prev.unwrap().0;
-        }
-        self.turns.push(turn);
-        if self.turns.len() % 100000 == 0 {
-            println!("{}", self.turns.len());
+        // the game needs a starting number, and turns are counted in a u32
+        if self.turn == 0 || self.turn == u32::MAX {
+            return None;
         }
-        //println!("{:?}", turn);
-        Some(turn)
+        let seen = self.last_seen.get(self.last).copied().unwrap_or(0);
+        let number = match seen {
+            0 => 0,
+            seen => (self.turn - seen) as usize,
+        };
+        self.speak(number);
+        Some(number)
     }
 }
 
-pub fn day15(args: &[String]) -> i32 {
-    println!("Day 15");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
+// the number spoken on the given (1-based) turn
+pub fn spoken(starting: &[usize], turn: usize) -> Option<usize> {
+    if turn <= starting.len() {
+        return turn.checked_sub(1).and_then(|t| starting.get(t)).copied();
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
-
-    let numbers = contents.lines()
-                          .next()
-                          .unwrap()
-                          .split(",")
-                          .map(|i| i.parse::<usize>().unwrap())
-                          .collect::<Vec<usize>>();
-    //println!("{:?}", numbers);
+    MemoryGame::with_capacity(starting, turn).nth(turn - starting.len() - 1)
+}
 
-    let g = MemoryGame::new(&numbers);
-    println!("Part 1: {}", g.skip(2020 - numbers.len() - 1).next().unwrap());
-    
-    let g = MemoryGame::new(&numbers);
-    println!("Part 2: {}", g.skip(30_000_000 - numbers.len() - 1).next().unwrap());
-    
-    0
+#[derive(Debug, Clone)]
+pub struct Day15 {
+    numbers: Vec<usize>,
 }
 
+impl Solution for Day15 {
 
+    fn parse(contents: &str) -> Result<Day15, AocError> {
+        let numbers = contents.lines()
+                              .next()
+                              .unwrap_or("")
+                              .split(',')
+                              .map(error::parse_number)
+                              .collect::<Result<Vec<usize>, _>>()
+                              .map_err(|e| e.at_line(1))?;
+        Ok(Day15 { numbers })
+    }
 
-// pub fn run(numbers: Vec<usize>, count: usize) -> usize {
+    fn part1(&self) -> Answer {
+        spoken(&self.numbers, 2020).into()
+    }
 
-// }
\ No newline at end of file
+    fn part2(&self) -> Answer {
+        spoken(&self.numbers, 30_000_000).into()
+    }
+}
@@ -1,5 +1,6 @@
-use std::fs;
 use regex::Regex;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Field {
@@ -14,19 +15,17 @@ impl Field {
         Field { name, lower, upper }
     }
 
-    pub fn parse(line: &str) -> Field {
+    pub fn parse(line: &str) -> Result<Field, AocError> {
         // ex "departure location: 36-363 or 377-962"
         lazy_static! {
             static ref FIELD: Regex = Regex::new(r"^([a-z\s]+):\s+(\d+)-(\d+)\s+or\s(\d+)-(\d+)$").unwrap();
         }
-        assert!(FIELD.is_match(&line));
-        let caps = FIELD.captures(line).unwrap();
-        let name = caps.get(1).unwrap().as_str().to_string();
-        let lower = (caps.get(2).unwrap().as_str().parse::<usize>().unwrap(),
-                     caps.get(3).unwrap().as_str().parse::<usize>().unwrap());
-        let upper = (caps.get(4).unwrap().as_str().parse::<usize>().unwrap(),
-                     caps.get(5).unwrap().as_str().parse::<usize>().unwrap());
-        Field { name, lower, upper }
+        let caps = FIELD.captures(line)
+                        .ok_or_else(|| AocError::parse("expected `name: a-b or c-d`"))?;
+        let name = caps[1].to_string();
+        let lower = (caps[2].parse::<usize>()?, caps[3].parse::<usize>()?);
+        let upper = (caps[4].parse::<usize>()?, caps[5].parse::<usize>()?);
+        Ok(Field { name, lower, upper })
     }
 
     fn check_range(range: (usize, usize), number: usize) -> bool {
@@ -42,18 +41,16 @@ impl Field {
 
 }
 
-pub fn parse_tickets(contents: &str) -> Vec<Vec<usize>> {
-    let mut tickets = vec![];
-    for (i, l) in contents.lines().enumerate() {
-        if i == 0 { continue; } // skip the first line "your ticket:"
-        tickets.push(l.split(",").map(|i| i.parse::<usize>().unwrap()).collect::<Vec<usize>>());
-    }
-    tickets
+pub fn parse_tickets(contents: &str) -> Result<Vec<Vec<usize>>, AocError> {
+    // skip the first line "your ticket:"
+    let tickets = contents.split_once('\n').map(|(_, t)| t).unwrap_or("");
+    error::parse_lines(tickets, |l| l.split(',').map(error::parse_number).collect())
+        .map_err(|e| e.offset_line(1))
 }
 
-pub fn invalid_tickets(fields: &Vec<Field>, nearby_tickets: &Vec<Vec<usize>> ) -> Vec<usize> {
+pub fn invalid_tickets(fields: &[Field], nearby_tickets: &[Vec<usize>]) -> Vec<usize> {
     let mut invalid: Vec<usize> = vec![];
-    for t in nearby_tickets.into_iter().flatten() {
+    for t in nearby_tickets.iter().flatten() {
         let mut valid = false;
         for f in fields {
             if f.is_valid(*t) {
@@ -68,8 +65,8 @@ pub fn invalid_tickets(fields: &Vec<Field>, nearby_tickets: &Vec<Vec<usize>> ) -
     invalid
 }
 
-pub fn identify_fields(fields: &Vec<Field>, tickets: &Vec<Vec<usize>>) -> Vec<Option<String>> {
-    let invalid = invalid_tickets(&fields, &tickets);
+pub fn identify_fields(fields: &[Field], tickets: &[Vec<usize>]) -> Vec<Option<String>> {
+    let invalid = invalid_tickets(fields, tickets);
     
     let mut found: Vec<Vec<String>> = vec![];
     for i in 0..fields.len() {
@@ -77,7 +74,7 @@ pub fn identify_fields(fields: &Vec<Field>, tickets: &Vec<Vec<usize>>) -> Vec<Op
         // the number of fields should equal the number of tickets in each line
         assert_eq!(fields.len(), tickets[i].len());
         // get all the tickets in the current column removing invalid
-        let column = tickets.into_iter().map(|t| t[i])
+        let column = tickets.iter().map(|t| t[i])
                                         .filter(|t| !invalid.contains(t))
                                         .collect::<Vec<usize>>();
         let mut valid = false;
@@ -107,52 +104,64 @@ pub fn identify_fields(fields: &Vec<Field>, tickets: &Vec<Vec<usize>>) -> Vec<Op
         assert!(name.is_some());
         // remove found name
         for f in &mut found {
-            let pos = f.iter().position(|n| Some(n) == name.as_ref());
-            if pos.is_some() {
-                f.remove(pos.unwrap());
+            if let Some(pos) = f.iter().position(|n| Some(n) == name.as_ref()) {
+                f.remove(pos);
             }
         }
     }
     result
 }
 
-pub fn day16(args: &[String]) -> i32 {
-    println!("Day 16");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
-
-    let groups: Vec<&str> = contents.split("\n\n").collect();
-    assert_eq!(groups.len(), 3);
-
-    let fields: Vec<Field> = groups[0].lines().map(|l| Field::parse(l)).collect();
-    //println!("{:?}", fields);
+#[derive(Debug, Clone)]
+pub struct Day16 {
+    fields: Vec<Field>,
+    your_ticket: Vec<usize>,
+    nearby_tickets: Vec<Vec<usize>>,
+}
 
-    let your_ticket = &parse_tickets(groups[1])[0]; // should only contaian 1 ticket line
-    //println!("{:?}", your_tickets);
+impl Solution for Day16 {
 
-    let nearby_tickets = parse_tickets(groups[2]);
-    //println!("{:?}", nearby_tickets);
+    fn parse(contents: &str) -> Result<Day16, AocError> {
+        let groups: Vec<&str> = contents.split("\n\n").collect();
+        if groups.len() != 3 {
+            return Err(AocError::parse("expected fields, your ticket and nearby tickets separated by blank lines"));
+        }
+        // line offsets of each group within the whole input
+        let (yours, nearby) = (groups[0].lines().count() + 1, groups[0].lines().count() + groups[1].lines().count() + 2);
 
-    let invalid = invalid_tickets(&fields, &nearby_tickets);
-    println!("Part 1: {:?}", invalid.iter().sum::<usize>());
+        let fields: Vec<Field> = error::parse_lines(groups[0], Field::parse)?;
+        let mut tickets = parse_tickets(groups[1]).map_err(|e| e.offset_line(yours))?;
+        if tickets.len() != 1 {
+            return Err(AocError::parse("expected exactly one ticket").at_line(yours + 1));
+        }
+        let your_ticket = tickets.remove(0);
+        let nearby_tickets = parse_tickets(groups[2]).map_err(|e| e.offset_line(nearby))?;
 
-    let mut alltickets = nearby_tickets.clone();
-    alltickets.push(your_ticket.clone());
+        // every ticket needs a value for each field
+        if your_ticket.len() != fields.len() {
+            return Err(AocError::parse(format!("expected {} values", fields.len())).at_line(yours + 2));
+        }
+        if let Some(i) = nearby_tickets.iter().position(|t| t.len() != fields.len()) {
+            return Err(AocError::parse(format!("expected {} values", fields.len())).at_line(nearby + i + 2));
+        }
+        Ok(Day16 { fields, your_ticket, nearby_tickets })
+    }
 
-    let found = identify_fields(&fields, &alltickets);
-     // get the values for "your ticket" where the field starts with "departure"
-    let values = found.into_iter()
-                         .enumerate().filter(|(_i, f)| f.as_ref().unwrap().find("departure") == Some(0))
-                         .map(|(i, _f)| your_ticket[i])
-                         .collect::<Vec<usize>>();
-    
-    println!("Part 2: {:?}", values.iter().product::<usize>());
+    fn part1(&self) -> Answer {
+        invalid_tickets(&self.fields, &self.nearby_tickets).iter().sum::<usize>().into()
+    }
 
-   0
-}
\ No newline at end of file
+    fn part2(&self) -> Answer {
+        let mut alltickets = self.nearby_tickets.clone();
+        alltickets.push(self.your_ticket.clone());
+
+        let found = identify_fields(&self.fields, &alltickets);
+        // get the values for "your ticket" where the field starts with "departure"
+        found.into_iter()
+             .enumerate()
+             .filter(|(_i, f)| f.as_ref().unwrap().starts_with("departure"))
+             .map(|(i, _f)| self.your_ticket[i])
+             .product::<usize>()
+             .into()
+    }
+}
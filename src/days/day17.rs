@@ -1,8 +1,9 @@
 use std::ops::Range;
-use std::fs;
 use std::fmt;
 use std::collections::HashMap;
 use itertools::iproduct;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum State {
@@ -11,11 +12,11 @@ pub enum State {
 }
 
 impl State {
-    pub fn parse(c: char) -> State {
+    pub fn parse(c: char) -> Result<State, AocError> {
         match c {
-            '#' => State::Active,
-            '.' => State::Inactive,
-            _ => unreachable!("Invalid option"),
+            '#' => Ok(State::Active),
+            '.' => Ok(State::Inactive),
+            _ => Err(AocError::parse(format!("expected `#` or `.`, found `{}`", c))),
         }
     }
 }
@@ -45,19 +46,24 @@ pub struct PocketDimension {
 
 impl PocketDimension {
 
-    pub fn new(input: &String, dimensions: Dimensions) -> PocketDimension {
-        let init: Vec<Vec<State>> = input.lines()
-                    .map(|l| l.chars().map(|c| State::parse(c)).collect())
-                    .collect();
-        assert_eq!(init.len(), init[0].len());
+    pub fn new(input: &str, dimensions: Dimensions) -> Result<PocketDimension, AocError> {
+        let init: Vec<Vec<State>> = error::parse_lines(input, |l| {
+            l.chars()
+             .enumerate()
+             .map(|(i, c)| State::parse(c).map_err(|e| e.at_column(i + 1)))
+             .collect()
+        })?;
+        if init.is_empty() || init.iter().any(|row| row.len() != init.len()) {
+            return Err(AocError::parse("expected a square initial slice"));
+        }
         let length = init.len() as isize;
         let mut map = HashMap::new();
-        input.lines().zip(PocketDimension::get_range(length))
-             .for_each(|(l, y)| 
-                l.chars().zip(PocketDimension::get_range(length))
-                 .for_each(|(c, x)| match map.insert((0, 0, y, x), State::parse(c)) {_ => ()} )
-             );
-        PocketDimension { map, length, dimensions }
+        init.iter().zip(PocketDimension::get_range(length))
+            .for_each(|(row, y)|
+                row.iter().zip(PocketDimension::get_range(length))
+                   .for_each(|(&s, x)| { map.insert((0, 0, y, x), s); })
+            );
+        Ok(PocketDimension { map, length, dimensions })
     }
 
     pub fn get_range(v: isize) -> Range<isize> {
@@ -70,7 +76,7 @@ impl PocketDimension {
 
     pub fn get(&self, w: isize, z: isize, y: isize, x: isize) -> State {
         match self.map.get(&(w, z, y, x)) {
-            Some(s) => s.clone(),
+            Some(s) => *s,
             _ => State::Inactive
         }
     }
@@ -117,9 +123,9 @@ impl PocketDimension {
         self.length += 2;
         match self.dimensions {
             Dimensions::Three => iproduct!(self.range(), self.range(), self.range())
-                                    .for_each(|(z, y, x)| match next.insert((0, z, y, x), self.next_state(0, z, y, x)) {_=>()}),
+                                    .for_each(|(z, y, x)| { next.insert((0, z, y, x), self.next_state(0, z, y, x)); }),
             Dimensions::Four => iproduct!(self.range(), self.range(), self.range(), self.range())
-                                    .for_each(|(w, z, y, x)| match next.insert((w, z, y, x), self.next_state(w, z, y, x)) {_=>()})
+                                    .for_each(|(w, z, y, x)| { next.insert((w, z, y, x), self.next_state(w, z, y, x)); })
         };
         self.map = next;
     }
@@ -163,28 +169,34 @@ impl fmt::Display for PocketDimension {
     }
 }
 
-pub fn day17(args: &[String]) -> i32 {
-    println!("Day 17");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
-
-    let mut p = PocketDimension::new(&contents, Dimensions::Three);
+// run the six boot cycles, returning the active cube count
+fn boot(mut p: PocketDimension) -> usize {
     for _r in 0..6 {
         p.run_round();
     }
-    println!("Part 1: {}", p.count());
+    p.count()
+}
 
-    let mut p = PocketDimension::new(&contents, Dimensions::Four);
-    for _r in 0..6 {
-        p.run_round();
+#[derive(Debug, Clone)]
+pub struct Day17 {
+    part1: PocketDimension,
+    part2: PocketDimension,
+}
+
+impl Solution for Day17 {
+
+    fn parse(contents: &str) -> Result<Day17, AocError> {
+        Ok(Day17 {
+            part1: PocketDimension::new(contents, Dimensions::Three)?,
+            part2: PocketDimension::new(contents, Dimensions::Four)?,
+        })
     }
-    println!("Part 2: {}", p.count());
 
-    0
-}
\ No newline at end of file
+    fn part1(&self) -> Answer {
+        boot(self.part1.clone()).into()
+    }
+
+    fn part2(&self) -> Answer {
+        boot(self.part2.clone()).into()
+    }
+}
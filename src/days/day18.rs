@@ -1,4 +1,5 @@
-use std::fs;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Part {
@@ -54,16 +55,26 @@ impl Operator {
     }
 }
 
+fn parse_operand(num: &str, column: usize) -> Result<RPNToken, AocError> {
+    num.parse::<i64>()
+       .map(RPNToken::Operand)
+       .map_err(|err| AocError::parse(format!("invalid number {}: {}", num, err)).at_column(column))
+}
+
 /// parse try to convert char into RPNToken
 /// it strip whitespace and support negative operations.
-pub fn parse(code: &str, part: Part) -> Result<Vec<RPNToken>, String> {
-    let tokens = code.chars().filter(|c| !c.is_whitespace());
+pub fn parse(code: &str, part: Part) -> Result<Vec<RPNToken>, AocError> {
+    let tokens = code.char_indices().filter(|(_i, c)| !c.is_whitespace());
     let mut output: Vec<RPNToken> = Vec::new();
     let mut stack: Vec<Operator> = Vec::new();
     let mut num: String = String::new();
+    let mut num_column = 0;
     let mut neg = true;
 
-    for tok in tokens {
+    for (i, tok) in tokens {
+        if num.is_empty() {
+            num_column = i + 1;
+        }
         if tok.is_numeric() {
             num.push(tok);
             neg = false;
@@ -74,8 +85,7 @@ pub fn parse(code: &str, part: Part) -> Result<Vec<RPNToken>, String> {
                 continue;
             }
             if !num.is_empty() {
-                let rpnt = RPNToken::Operand(num.parse::<i64>().map_err(|err| err.to_string())?);
-                output.push(rpnt);
+                output.push(parse_operand(&num, num_column)?);
                 num.clear();
             }
 
@@ -85,12 +95,12 @@ pub fn parse(code: &str, part: Part) -> Result<Vec<RPNToken>, String> {
                     neg = true;
                 },
                 Some(Operator::RPAREN) => {
-                    while let Some(v) = stack.pop() {
-                        if v == Operator::LPAREN {
-                            break
+                    loop {
+                        match stack.pop() {
+                            Some(Operator::LPAREN) => break,
+                            Some(v) => output.push(RPNToken::Operator(v)),
+                            None => return Err(AocError::parse("unmatched `)`").at_column(i + 1)),
                         }
-                        assert_ne!(v, Operator::RPAREN);
-                        output.push(RPNToken::Operator(v));
                     }
                 },
                 Some(tokop) => {
@@ -106,20 +116,35 @@ pub fn parse(code: &str, part: Part) -> Result<Vec<RPNToken>, String> {
                     stack.push(tokop);
                     neg = true;
                 },
-                None => return Err(format!("Unexpected character: {}", tok)),
+                None => return Err(AocError::parse(format!("unexpected character `{}`", tok)).at_column(i + 1)),
             }
         }
     }
 
     if !num.is_empty() {
-        let rpnt = RPNToken::Operand(num.parse::<i64>().map_err(|err| err.to_string())?);
-        output.push(rpnt);
+        output.push(parse_operand(&num, num_column)?);
     }
 
     while let Some(v) = stack.pop() {
+        if v == Operator::LPAREN {
+            return Err(AocError::parse("unmatched `(`"));
+        }
         output.push(RPNToken::Operator(v));
     }
 
+    // every operator needs two operands, leaving a single result
+    let mut depth = 0;
+    for t in &output {
+        depth = match t {
+            RPNToken::Operand(_) => depth + 1,
+            RPNToken::Operator(_) if depth >= 2 => depth - 1,
+            RPNToken::Operator(_) => return Err(AocError::parse("missing operand")),
+        };
+    }
+    if depth != 1 {
+        return Err(AocError::parse("expected a single expression"));
+    }
+
     Ok(output)
 }
 
@@ -153,22 +178,23 @@ pub fn evaluate(code: &str, part: Part) -> i64 {
     }
 }
 
-pub fn day18(args: &[String]) -> i32 {
-    println!("Day 18");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
+#[derive(Debug, Clone)]
+pub struct Day18 {
+    lines: Vec<String>,
+}
 
-    let results = contents.lines().map(|l| evaluate(l, Part::Part1)).collect::<Vec<i64>>();
-    println!("Part 1: {}", results.iter().sum::<i64>());
+impl Solution for Day18 {
 
-    let results = contents.lines().map(|l| evaluate(l, Part::Part2)).collect::<Vec<i64>>();
-    println!("Part 2: {}", results.iter().sum::<i64>());
+    fn parse(contents: &str) -> Result<Day18, AocError> {
+        let lines = error::parse_lines(contents, |l| parse(l, Part::Part1).map(|_| l.to_string()))?;
+        Ok(Day18 { lines })
+    }
 
-    0
-}
\ No newline at end of file
+    fn part1(&self) -> Answer {
+        self.lines.iter().map(|l| evaluate(l, Part::Part1)).sum::<i64>().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.lines.iter().map(|l| evaluate(l, Part::Part2)).sum::<i64>().into()
+    }
+}
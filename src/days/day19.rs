@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use regex::Regex;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    Char(char),
+    // any one of the sequences of sub rules
+    Alternatives(Vec<Vec<usize>>),
+}
+
+impl Rule {
+
+    pub fn parse(line: &str) -> Result<(usize, Rule), AocError> {
+        // ex "1: 2 3 | 3 2" or "4: \"a\""
+        lazy_static! {
+            static ref CHAR: Regex = Regex::new(r#"^(\d+): "([a-z])"$"#).unwrap();
+            static ref SUBRULES: Regex = Regex::new(r"^(\d+):((?: \d+)+(?: \|(?: \d+)+)*)$").unwrap();
+        }
+        if let Some(caps) = CHAR.captures(line) {
+            return Ok((caps[1].parse()?, Rule::Char(caps[2].chars().next().unwrap())));
+        }
+        let caps = SUBRULES.captures(line)
+                           .ok_or_else(|| AocError::parse("expected `n: \"c\"` or `n: a b | c d`"))?;
+        let alternatives = caps[2].split('|')
+                                  .map(|seq| seq.split_whitespace().map(error::parse_number).collect())
+                                  .collect::<Result<_, _>>()?;
+        Ok((caps[1].parse()?, Rule::Alternatives(alternatives)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    rules: HashMap<usize, Rule>,
+}
+
+impl Rules {
+
+    pub fn new(rules: HashMap<usize, Rule>) -> Rules {
+        Rules { rules }
+    }
+
+    pub fn parse(contents: &str) -> Result<Rules, AocError> {
+        let parsed = error::parse_lines(contents, Rule::parse)?;
+        let mut rules = HashMap::new();
+        for (i, (n, rule)) in parsed.iter().enumerate() {
+            if rules.insert(*n, rule.clone()).is_some() {
+                return Err(AocError::parse(format!("rule {} is defined twice", n)).at_line(i + 1));
+            }
+        }
+        // every referenced rule has to be defined
+        for (i, (_, rule)) in parsed.iter().enumerate() {
+            if let Rule::Alternatives(alternatives) = rule {
+                if let Some(n) = alternatives.iter().flatten().find(|n| !rules.contains_key(n)) {
+                    return Err(AocError::parse(format!("undefined rule {}", n)).at_line(i + 1));
+                }
+            }
+        }
+        Ok(Rules { rules })
+    }
+
+    pub fn get(&self, number: usize) -> Option<&Rule> {
+        self.rules.get(&number)
+    }
+
+    pub fn insert(&mut self, number: usize, rule: Rule) {
+        self.rules.insert(number, rule);
+    }
+
+    // The positions in `message` where a match of `rule` starting at `start`
+    // can end. Every rule consumes at least one character before it recurses,
+    // so looping rules such as "8: 42 | 42 8" terminate at the end of the message.
+    fn ends(&self, rule: usize, message: &[char], start: usize) -> Vec<usize> {
+        if start >= message.len() {
+            return vec![];
+        }
+        match self.rules.get(&rule) {
+            Some(Rule::Char(c)) => {
+                if message[start] == *c {
+                    vec![start + 1]
+                } else {
+                    vec![]
+                }
+            },
+            Some(Rule::Alternatives(alternatives)) => {
+                let mut ends = vec![];
+                for seq in alternatives {
+                    let mut positions = vec![start];
+                    for &sub in seq {
+                        positions = positions.iter()
+                                             .flat_map(|&p| self.ends(sub, message, p))
+                                             .collect();
+                        if positions.is_empty() {
+                            break;
+                        }
+                    }
+                    ends.extend(positions);
+                }
+                ends
+            },
+            None => vec![],
+        }
+    }
+
+    pub fn matches(&self, message: &str) -> bool {
+        let message: Vec<char> = message.chars().collect();
+        self.ends(0, &message, 0).contains(&message.len())
+    }
+
+    // Replace rules 8 and 11 with their looping versions
+    pub fn with_loops(&self) -> Rules {
+        let mut rules = self.clone();
+        rules.insert(8, Rule::Alternatives(vec![vec![42], vec![42, 8]]));
+        rules.insert(11, Rule::Alternatives(vec![vec![42, 31], vec![42, 11, 31]]));
+        rules
+    }
+}
+
+pub fn count_matches(rules: &Rules, messages: &[String]) -> usize {
+    messages.iter().filter(|m| rules.matches(m)).count()
+}
+
+#[derive(Debug, Clone)]
+pub struct Day19 {
+    rules: Rules,
+    messages: Vec<String>,
+}
+
+impl Solution for Day19 {
+
+    fn parse(contents: &str) -> Result<Day19, AocError> {
+        let (rules, messages) = contents.split_once("\n\n")
+                                        .ok_or_else(|| AocError::parse("expected rules and messages separated by a blank line"))?;
+        let rules = Rules::parse(rules)?;
+        let offset = contents.lines().take_while(|l| !l.is_empty()).count() + 1;
+        let messages = error::parse_lines(messages, |m| {
+            if m.is_empty() || !m.chars().all(|c| c.is_ascii_lowercase()) {
+                return Err(AocError::parse(format!("expected a message, found `{}`", m)));
+            }
+            Ok(m.to_string())
+        }).map_err(|e| e.offset_line(offset))?;
+        Ok(Day19 { rules, messages })
+    }
+
+    fn part1(&self) -> Answer {
+        count_matches(&self.rules, &self.messages).into()
+    }
+
+    fn part2(&self) -> Answer {
+        // the looping rules are built from 42 and 31, which the small examples lack
+        if self.rules.get(42).is_none() || self.rules.get(31).is_none() {
+            return Answer::Unsolved;
+        }
+        count_matches(&self.rules.with_loops(), &self.messages).into()
+    }
+}
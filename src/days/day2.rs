@@ -1,5 +1,6 @@
-use std::fs;
 use regex::Regex;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Policy {
@@ -13,54 +14,59 @@ impl Policy {
         Policy { nums, letter }
     }
 
-    pub fn parse(policypassword: &str) -> (Policy, String) {
+    pub fn parse(policypassword: &str) -> Result<(Policy, String), AocError> {
         lazy_static! {
-            static ref RE: Regex = Regex::new(r"(\d+)-(\d+) ([a-z]): ([a-z]+)").unwrap();
+            static ref RE: Regex = Regex::new(r"^(\d+)-(\d+) ([a-z]): ([a-z]+)$").unwrap();
         }
         // Split out the policy & password line (ex: "1-3 a: abcde")
-        let caps = RE.captures(policypassword).unwrap();
-        let first = caps.get(1).unwrap().as_str().parse::<usize>().unwrap();
-        let second = caps.get(2).unwrap().as_str().parse::<usize>().unwrap();
-        let letter = caps.get(3).unwrap().as_str().chars().next().unwrap();
-        let password = caps.get(4).unwrap().as_str();
+        let caps = RE.captures(policypassword)
+                     .ok_or_else(|| AocError::parse("expected `a-b c: password`"))?;
+        let first = caps[1].parse::<usize>()?;
+        let second = caps[2].parse::<usize>()?;
+        let letter = caps[3].chars().next().unwrap();
+        let password = &caps[4];
+        if first == 0 || second == 0 {
+            return Err(AocError::parse("policy positions start at 1"));
+        }
 
         let policy = Policy::new((first, second), letter);
-        (policy, password.to_string())
+        Ok((policy, password.to_string()))
     }
 
     //The password policy indicates the lowest and highest number of times a given letter must appear
-    pub fn is_valid_part1(&self, password: &String) -> bool {
-        let count = password.chars().filter(|&c| c == self.letter).count() as usize;
-        //(self.nums.0..self.nums.1+1).contains(&count)
-        (count >= self.nums.0) && (count <= self.nums.1)
+    pub fn is_valid_part1(&self, password: &str) -> bool {
+        let count = password.chars().filter(|&c| c == self.letter).count();
+        (self.nums.0..=self.nums.1).contains(&count)
     }
 
     //policy actually describes two positions in the password
-    pub fn is_valid_part2(&self, password: &String) -> bool {
+    pub fn is_valid_part2(&self, password: &str) -> bool {
         let m = (
-            password.chars().nth(self.nums.0-1).unwrap() == self.letter,
-            password.chars().nth(self.nums.1-1).unwrap() == self.letter,
+            password.chars().nth(self.nums.0-1) == Some(self.letter),
+            password.chars().nth(self.nums.1-1) == Some(self.letter),
         );
         // valid if there is only 1 match
         (m.0 && !m.1) || (!m.0 && m.1)
     }
 }
 
-pub fn day2(args: &[String]) -> i32 {
-    println!("Day 2");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
+#[derive(Debug, Clone)]
+pub struct Day2 {
+    passwords: Vec<(Policy, String)>,
+}
+
+impl Solution for Day2 {
+
+    fn parse(contents: &str) -> Result<Day2, AocError> {
+        let passwords = error::parse_lines(contents, Policy::parse)?;
+        Ok(Day2 { passwords })
+    }
+
+    fn part1(&self) -> Answer {
+        self.passwords.iter().filter(|(p, pwd)| p.is_valid_part1(pwd)).count().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.passwords.iter().filter(|(p, pwd)| p.is_valid_part2(pwd)).count().into()
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
-    
-    let passwords: Vec<(Policy, String)> = contents.lines().map(|l| Policy::parse(l)).collect();
-    //println!("{:?}", result);
-    
-    println!("Part 1: {:}", passwords.iter().filter(|(p, pwd)| p.is_valid_part1(pwd)).count());
-    println!("Part 2: {:}", passwords.iter().filter(|(p, pwd)| p.is_valid_part2(pwd)).count());
-    0
 }
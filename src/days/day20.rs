@@ -1,9 +1,10 @@
-use std::fs;
 use std::fmt;
 use regex::Regex;
 use itertools::iproduct;
 use std::collections::HashMap;
 use itertools::concat;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Edge {
@@ -40,9 +41,9 @@ impl Image {
     pub fn get_edge(&self, edge: Edge) -> Vec<char> {
         match edge {
             Edge::Top => self.image.first().unwrap().to_vec(),
-            Edge::Right => self.image.iter().map(|r| r.last().unwrap().clone()).collect(),
+            Edge::Right => self.image.iter().map(|r| *r.last().unwrap()).collect(),
             Edge::Bottom => self.image.last().unwrap().to_vec(),
-            Edge::Left => self.image.iter().map(|r| r.first().unwrap().clone()).collect(),
+            Edge::Left => self.image.iter().map(|r| *r.first().unwrap()).collect(),
         }
     }
 
@@ -81,10 +82,8 @@ impl Image {
         }
         //println!("{:?}", offset);
         for (y, x) in iproduct!(0..input.height(), 0..input.width()) {
-            if input.image[y][x] == '#' {
-                if self.image[offset.0+y][offset.1+x] != '#' {
-                    return false;
-                }
+            if input.image[y][x] == '#' && self.image[offset.0+y][offset.1+x] != '#' {
+                return false;
             }
         }
         true
@@ -131,7 +130,11 @@ impl Tile {
         Tile { id, image }
     }
 
-    pub fn parse(lines: &str) -> Tile {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn parse(lines: &str) -> Result<Tile, AocError> {
         // ex:
         //    Tile 2311:
         //    ..##.#..#.
@@ -147,14 +150,22 @@ impl Tile {
         lazy_static! {
             static ref ID: Regex = Regex::new(r"^Tile (\d+):$").unwrap();
         }
-        if let Some((first, rows)) = lines.lines().collect::<Vec<&str>>().split_first() {
-            assert!(ID.is_match(first));
-            let caps = ID.captures(first).unwrap();
-            let id = caps.get(1).unwrap().as_str().parse::<usize>().unwrap();
-            let image = Image::parse(&rows.join("\n"));
-            return Tile { id, image };
+        let (first, rows) = match lines.split_once('\n') {
+            Some((first, rows)) => (first, rows),
+            None => (lines, ""),
+        };
+        let caps = ID.captures(first)
+                     .ok_or_else(|| AocError::parse("expected `Tile <id>:`").at_line(1))?;
+        let id = caps[1].parse::<usize>().map_err(|e| AocError::from(e).at_line(1))?;
+        // image rows follow the id line
+        let widths = error::parse_lines(rows, |l| match l.find(|c| c != '.' && c != '#') {
+            Some(i) => Err(AocError::parse("expected `.` or `#`").at_column(i + 1)),
+            None => Ok(l.len()),
+        }).map_err(|e| e.offset_line(1))?;
+        if widths.is_empty() || widths.iter().any(|&w| w != widths.len()) {
+            return Err(AocError::parse("expected a square tile image").at_line(2));
         }
-        unreachable!("Failed to parse");
+        Ok(Tile { id, image: Image::parse(rows) })
     }
 
 }
@@ -182,7 +193,7 @@ impl Arrangement {
     }
 
     fn edge_counts(&self) -> HashMap::<String, usize> {
-        let edges = vec![Edge::Top, Edge::Right, Edge::Bottom, Edge::Left];
+        let edges = [Edge::Top, Edge::Right, Edge::Bottom, Edge::Left];
         let rotations = 0..4;
 
         //count the occurances of each edge
@@ -191,10 +202,7 @@ impl Arrangement {
             let i = tile.image.orient(rotations, flipped);
             for edge in &edges {
                 let e = i.get_edge_string(*edge);
-                match counts.get_mut(&e) {
-                    Some(c) => *c+=1,
-                    None => match counts.insert(e, 1) {_=>()},
-                };
+                *counts.entry(e).or_insert(0) += 1;
             }
         }
         counts
@@ -211,7 +219,7 @@ impl Arrangement {
         // find the 4 tiles with two outer edges
         let mut corners = Vec::new();
         for tile in self.tiles.iter() {
-            let count = vec![Edge::Top, Edge::Right, Edge::Bottom, Edge::Left]
+            let count = [Edge::Top, Edge::Right, Edge::Bottom, Edge::Left]
                                 .iter().map(|e| tile.image.get_edge_string(*e))
                                 .filter(|e| outer.contains(e))
                                 .count();
@@ -243,9 +251,8 @@ impl Arrangement {
         for (y, x) in iproduct!(0..self.height(), 0..self.width()) {
             //println!("{} {} {:?} {}", y, x, edge, matching_edge.iter().collect::<String>());
             let t = self.find_tile(edge, matching_edge, &available_tiles);
-            let pos = available_tiles.iter().position(|n| n.id == t.as_ref().unwrap().id);
-            if pos.is_some() {
-                available_tiles.remove(pos.unwrap());
+            if let Some(pos) = available_tiles.iter().position(|n| n.id == t.as_ref().unwrap().id) {
+                available_tiles.remove(pos);
             }
             self.image[y][x] = t;
             if x == self.width()-1 {
@@ -258,7 +265,7 @@ impl Arrangement {
         }
     }
 
-    fn find_tile(&self, edge: Edge, matching_edge: Vec<char>, available_tiles: &Vec<Tile>) -> Option<Tile> {
+    fn find_tile(&self, edge: Edge, matching_edge: Vec<char>, available_tiles: &[Tile]) -> Option<Tile> {
         for (rotation, flipped, tile) in iproduct!(0..4, vec![false, true], available_tiles) {
             let i = tile.image.orient(rotation, flipped);
             if i.get_edge(edge) == matching_edge {
@@ -290,42 +297,46 @@ impl Arrangement {
 
 }
 
-pub fn day20(args: &[String]) -> i32 {
-    println!("Day 20");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
-
-    let tiles: Vec<Tile> = contents.split("\n\n")
-                                   .map(|s| Tile::parse(s))
-                                   .collect();
-
-    let mut arrangement = Arrangement::new(tiles.clone());
-    println!("Part 1: {}", arrangement.find_corners().iter().map(|t| t.id).product::<usize>());
-
-    arrangement.build_image();
-    let result = arrangement.get_result();
-    //println!("{}", result.orient(1, true));
-
-    let monster = Image::parse(
-        ["                  # ",
-         "#    ##    ##    ###",
-         " #  #  #  #  #  #   "].join("\n").as_str()
-    );
-    let monster_char_count = monster.count_char('#');
-
-    for (rotations, flipped) in iproduct!(0..4, vec![false, true]) {
-        let r = result.orient(rotations, flipped);
-        let found_count = r.find_image_positions(&monster).len();
-        if found_count > 0 {
-            println!("Part 2: {:?}", r.count_char('#') - monster_char_count * found_count);
+#[derive(Debug, Clone)]
+pub struct Day20 {
+    tiles: Vec<Tile>,
+}
+
+impl Solution for Day20 {
+
+    fn parse(contents: &str) -> Result<Day20, AocError> {
+        let tiles: Vec<Tile> = error::parse_groups(contents.trim_end(), Tile::parse)?;
+        let width = (tiles.len() as f64).sqrt() as usize;
+        if width * width != tiles.len() {
+            return Err(AocError::parse(format!("expected a square number of tiles, found {}", tiles.len())));
         }
+        Ok(Day20 { tiles })
+    }
+
+    fn part1(&self) -> Answer {
+        let arrangement = Arrangement::new(self.tiles.clone());
+        arrangement.find_corners().iter().map(|t| t.id).product::<usize>().into()
     }
 
-    0
+    fn part2(&self) -> Answer {
+        let mut arrangement = Arrangement::new(self.tiles.clone());
+        arrangement.build_image();
+        let result = arrangement.get_result();
+
+        let monster = Image::parse(
+            ["                  # ",
+             "#    ##    ##    ###",
+             " #  #  #  #  #  #   "].join("\n").as_str()
+        );
+        let monster_char_count = monster.count_char('#');
+
+        for (rotations, flipped) in iproduct!(0..4, vec![false, true]) {
+            let r = result.orient(rotations, flipped);
+            let found_count = r.find_image_positions(&monster).len();
+            if found_count > 0 {
+                return (r.count_char('#') - monster_char_count * found_count).into();
+            }
+        }
+        Answer::Unsolved
+    }
 }
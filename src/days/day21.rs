@@ -0,0 +1,95 @@
+use std::collections::{BTreeMap, HashSet};
+use regex::Regex;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Food {
+    ingredients: Vec<String>,
+    allergens: Vec<String>,
+}
+
+impl Food {
+
+    pub fn new(ingredients: Vec<String>, allergens: Vec<String>) -> Food {
+        Food { ingredients, allergens }
+    }
+
+    pub fn parse(line: &str) -> Result<Food, AocError> {
+        // ex "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)"
+        lazy_static! {
+            static ref FOOD: Regex = Regex::new(r"^([a-z]+(?: [a-z]+)*)(?: \(contains ([a-z]+(?:, [a-z]+)*)\))?$").unwrap();
+        }
+        let caps = FOOD.captures(line)
+                       .ok_or_else(|| AocError::parse("expected `ingredients (contains allergens)`"))?;
+        let ingredients = caps[1].split(' ').map(|i| i.to_string()).collect();
+        let allergens = caps.get(2)
+                            .map(|a| a.as_str().split(", ").map(|a| a.to_string()).collect())
+                            .unwrap_or_default();
+        Ok(Food { ingredients, allergens })
+    }
+}
+
+// For each allergen, the ingredients present in every food listing it
+pub fn candidates(foods: &[Food]) -> BTreeMap<String, HashSet<String>> {
+    let mut candidates: BTreeMap<String, HashSet<String>> = BTreeMap::new();
+    for food in foods {
+        let ingredients: HashSet<String> = food.ingredients.iter().cloned().collect();
+        for allergen in &food.allergens {
+            candidates.entry(allergen.clone())
+                      .and_modify(|c| c.retain(|i| ingredients.contains(i)))
+                      .or_insert_with(|| ingredients.clone());
+        }
+    }
+    candidates
+}
+
+// Repeatedly assign allergens with a single candidate ingredient and remove
+// that ingredient from the others. None if the constraints can't be resolved.
+pub fn solve(foods: &[Food]) -> Option<BTreeMap<String, String>> {
+    let mut candidates = candidates(foods);
+    let mut dangerous = BTreeMap::new();
+    while !candidates.is_empty() {
+        let (allergen, ingredient) = candidates.iter()
+                                               .find(|(_a, c)| c.len() == 1)
+                                               .map(|(a, c)| (a.clone(), c.iter().next().unwrap().clone()))?;
+        candidates.remove(&allergen);
+        candidates.values_mut().for_each(|c| { c.remove(&ingredient); });
+        dangerous.insert(allergen, ingredient);
+    }
+    Some(dangerous)
+}
+
+// Count appearances of ingredients that can't contain any allergen
+pub fn safe_count(foods: &[Food]) -> usize {
+    let unsafe_ingredients: HashSet<String> = candidates(foods).into_values().flatten().collect();
+    foods.iter()
+         .flat_map(|f| f.ingredients.iter())
+         .filter(|i| !unsafe_ingredients.contains(*i))
+         .count()
+}
+
+// Dangerous ingredients sorted by their allergen
+pub fn canonical_dangerous(foods: &[Food]) -> Option<String> {
+    Some(solve(foods)?.into_values().collect::<Vec<String>>().join(","))
+}
+
+#[derive(Debug, Clone)]
+pub struct Day21 {
+    foods: Vec<Food>,
+}
+
+impl Solution for Day21 {
+
+    fn parse(contents: &str) -> Result<Day21, AocError> {
+        Ok(Day21 { foods: error::parse_lines(contents, Food::parse)? })
+    }
+
+    fn part1(&self) -> Answer {
+        safe_count(&self.foods).into()
+    }
+
+    fn part2(&self) -> Answer {
+        canonical_dangerous(&self.foods).into()
+    }
+}
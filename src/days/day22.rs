@@ -1,5 +1,7 @@
-use std::fs;
 use std::cmp::max;
+use std::collections::HashSet;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Player {
@@ -13,13 +15,14 @@ impl Player {
         Player { name, cards }
     }
 
-    pub fn parse(lines: &str) -> Player {
-        if let Some((first, rows)) = lines.lines().collect::<Vec<&str>>().split_first() {
-            let name = first.to_string();
-            let cards = rows.iter().map(|l| l.parse::<u8>().unwrap()).collect();
-            return Player { name, cards };
-        }
-        unreachable!("Failed to parse");
+    pub fn parse(lines: &str) -> Result<Player, AocError> {
+        let (first, rows) = lines.split_once('\n').unwrap_or((lines, ""));
+        let name = first.strip_suffix(':')
+                        .ok_or_else(|| AocError::parse("expected `Player <n>:`").at_line(1))?
+                        .to_string();
+        let cards = error::parse_lines(rows, error::parse_number)
+                          .map_err(|e| e.offset_line(1))?;
+        Ok(Player { name, cards })
     }
 
     pub fn has_cards(&self) -> bool {
@@ -63,23 +66,78 @@ pub fn part1(mut player1: Player, mut player2: Player) -> usize {
     max(player1.score(), player2.score())
 }
 
-pub fn day22(args: &[String]) -> i32 {
-    println!("Day 22");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Winner {
+    Player1,
+    Player2,
+}
+
+pub fn recursive_combat(player1: &mut Player, player2: &mut Player) -> Winner {
+    let mut seen = HashSet::new();
+    while player1.has_cards() && player2.has_cards() {
+        // repeating an earlier state of this game means player 1 wins it,
+        // otherwise the game could go on forever
+        if !seen.insert((player1.cards.clone(), player2.cards.clone())) {
+            return Winner::Player1;
+        }
+        let (card1, card2) = (player1.play_card(), player2.play_card());
+        let winner = if player1.cards.len() >= card1 as usize && player2.cards.len() >= card2 as usize {
+            // both have enough cards left to recurse into a sub-game
+            let mut sub1 = Player::new(player1.name.clone(), player1.cards[..card1 as usize].to_vec());
+            let mut sub2 = Player::new(player2.name.clone(), player2.cards[..card2 as usize].to_vec());
+            recursive_combat(&mut sub1, &mut sub2)
+        } else if card1 > card2 {
+            Winner::Player1
+        } else {
+            Winner::Player2
+        };
+        match winner {
+            Winner::Player1 => player1.add_cards(card1, card2),
+            Winner::Player2 => player2.add_cards(card2, card1),
+        }
+    }
+    if player1.has_cards() {
+        Winner::Player1
+    } else {
+        Winner::Player2
+    }
+}
+
+pub fn part2(mut player1: Player, mut player2: Player) -> usize {
+    match recursive_combat(&mut player1, &mut player2) {
+        Winner::Player1 => player1.score(),
+        Winner::Player2 => player2.score(),
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
-
-    let mut players: Vec<Player> = contents.split("\n\n")
-                                   .map(|l| Player::parse(l))
-                                   .collect();
-    let (player1, player2) = (players.remove(0), players.remove(0)); 
-    //println!("{:?} {:?}", player1, player2);
-    
-    println!("Part 1: {}", part1(player1.clone(), player2.clone()));
-    0
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone)]
+pub struct Day22 {
+    player1: Player,
+    player2: Player,
+}
+
+impl Solution for Day22 {
+
+    fn parse(contents: &str) -> Result<Day22, AocError> {
+        let mut players: Vec<Player> = error::parse_groups(contents.trim_end(), Player::parse)?;
+        if players.len() != 2 {
+            return Err(AocError::parse(format!("expected 2 players, found {}", players.len())));
+        }
+        // a round can't be decided between two equal cards
+        let mut cards = players.iter().flat_map(|p| p.cards.clone()).collect::<Vec<u8>>();
+        cards.sort_unstable();
+        if let Some(w) = cards.windows(2).find(|w| w[0] == w[1]) {
+            return Err(AocError::parse(format!("card {} appears more than once", w[0])));
+        }
+        let (player1, player2) = (players.remove(0), players.remove(0));
+        Ok(Day22 { player1, player2 })
+    }
+
+    fn part1(&self) -> Answer {
+        part1(self.player1.clone(), self.player2.clone()).into()
+    }
+
+    fn part2(&self) -> Answer {
+        part2(self.player1.clone(), self.player2.clone()).into()
+    }
+}
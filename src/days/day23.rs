@@ -1,78 +1,152 @@
-use std::fs;
+use crate::days::{Answer, Solution};
+use crate::error::AocError;
 
+pub const PART1_MOVES: usize = 100;
+pub const PART2_CUPS: usize = 1_000_000;
+pub const PART2_MOVES: usize = 10_000_000;
+
+// The circle is stored as a successor array: `next[c]` is the cup clockwise
+// of cup `c` (index 0 is unused), so a move only relinks a few cups.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CrabCups {
-    cups: Vec<u8>
+    next: Vec<u32>,
+    current: u32,
 }
 
 impl CrabCups {
 
-    pub fn new(cups: Vec<u8>) -> CrabCups {
-        CrabCups { cups }
+    // `cups` must be each of the labels 1 to n exactly once, with at least 5 cups
+    pub fn new(cups: &[usize]) -> CrabCups {
+        CrabCups::with_total(cups, cups.len())
+    }
+
+    // Start with `cups` followed by the cups labelled cups.len() + 1 to `total`
+    pub fn with_total(cups: &[usize], total: usize) -> CrabCups {
+        assert!(total >= 5 && cups.len() <= total, "Invalid cup count");
+        let mut next = vec![0u32; total + 1];
+        let order = cups.iter().cloned().chain(cups.len() + 1..=total);
+        let first = cups.first().cloned().unwrap_or(1);
+        let mut last = first;
+        for cup in order.skip(1) {
+            next[last] = cup as u32;
+            last = cup;
+        }
+        next[last] = first as u32;
+        CrabCups { next, current: first as u32 }
+    }
+
+    pub fn parse(line: &str) -> Result<CrabCups, AocError> {
+        let cups = line.chars()
+                       .enumerate()
+                       .map(|(i, c)| match c.to_digit(10) {
+                           Some(d) if d > 0 => Ok(d as usize),
+                           _ => Err(AocError::parse(format!("expected a cup label 1 to 9, found `{}`", c)).at_column(i + 1)),
+                       })
+                       .collect::<Result<Vec<usize>, _>>()?;
+        let mut sorted = cups.clone();
+        sorted.sort_unstable();
+        if cups.len() < 5 || sorted != (1..=cups.len()).collect::<Vec<usize>>() {
+            return Err(AocError::parse("expected each of the cups 1 to n exactly once, with at least 5 cups"));
+        }
+        Ok(CrabCups::new(&cups))
+    }
+
+    pub fn len(&self) -> usize {
+        self.next.len() - 1
     }
 
-    pub fn parse(line: &str) -> CrabCups {
-        let cups = line.chars().map(|c| c.to_string().parse::<u8>().unwrap()).collect();
-        CrabCups { cups }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
     pub fn next(&mut self) {
-        let (first, pickup, mut remaining) = (self.cups.get(0).unwrap(), self.cups.get(1..4).unwrap(), self.cups.get(4..9).unwrap().to_vec());
-        //println!("{:?} {:?} {:?}", first, pickup, remaining);
-        let mut find = *first;
-        let mut pos;
+        let n = self.len() as u32;
+        let current = self.current;
+        let p1 = self.next[current as usize];
+        let p2 = self.next[p1 as usize];
+        let p3 = self.next[p2 as usize];
+        // pick up the three cups after the current one
+        self.next[current as usize] = self.next[p3 as usize];
+        let mut destination = current;
         loop {
-            find -= 1;
-            if find == 0 {
-                find = 9;
-            }
-            pos = remaining.iter().position(|&i| i == find);
-            if pos.is_some() {
+            destination = if destination == 1 { n } else { destination - 1 };
+            if destination != p1 && destination != p2 && destination != p3 {
                 break;
             }
         }
-        //println!("max: {:?}", pos);
-        pickup.iter().rev().for_each(|&i| remaining.insert(pos.unwrap() + 1, i));
-        remaining.push(*first);
-        self.cups = remaining;
-        //println!("{:?}", self.cups);
+        // and put them back after the destination
+        self.next[p3 as usize] = self.next[destination as usize];
+        self.next[destination as usize] = p1;
+        self.current = self.next[current as usize];
     }
 
-    pub fn answer(&self) -> String {
-        match self.cups.iter().position(|&n| n == 1) {
-            Some(pos) => {
-                String::from(format!("{}{}", 
-                    CrabCups::vec2str(&self.cups[pos+1..].to_vec()), 
-                    CrabCups::vec2str(&self.cups[0..pos].to_vec()))
-                )
-            },
-            None => String::from("No answer?")
+    pub fn play(&mut self, moves: usize) {
+        for _i in 0..moves {
+            self.next();
+        }
+    }
+
+    // Labels of the `count` cups clockwise after cup `cup`
+    pub fn after(&self, cup: usize, count: usize) -> Vec<usize> {
+        let mut cups = Vec::with_capacity(count);
+        let mut c = cup;
+        for _i in 0..count {
+            c = self.next[c] as usize;
+            cups.push(c);
         }
+        cups
+    }
+
+    // All cups clockwise, starting from the current cup
+    pub fn cups(&self) -> Vec<usize> {
+        let mut cups = vec![self.current as usize];
+        cups.extend(self.after(self.current as usize, self.len() - 1));
+        cups
     }
 
-    pub fn vec2str(vec: &Vec<u8>) -> String {
+    pub fn answer(&self) -> String {
+        CrabCups::vec2str(&self.after(1, self.len() - 1))
+    }
+
+    // Product of the two cups after cup 1
+    pub fn star_product(&self) -> u64 {
+        self.after(1, 2).iter().map(|&c| c as u64).product()
+    }
+
+    pub fn vec2str(vec: &[usize]) -> String {
         vec.iter().map(|i| format!("{}", i)).collect::<Vec<String>>().join("")
     }
 
 }
 
-pub fn day23(args: &[String]) -> i32 {
-    println!("Day 23");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
+pub fn part1(game: &CrabCups, moves: usize) -> String {
+    let mut game = game.clone();
+    game.play(moves);
+    game.answer()
+}
+
+pub fn part2(game: &CrabCups, total: usize, moves: usize) -> u64 {
+    let mut game = CrabCups::with_total(&game.cups(), total);
+    game.play(moves);
+    game.star_product()
+}
+
+#[derive(Debug, Clone)]
+pub struct Day23 {
+    game: CrabCups,
+}
+
+impl Solution for Day23 {
+
+    fn parse(contents: &str) -> Result<Day23, AocError> {
+        Ok(Day23 { game: CrabCups::parse(contents.trim()).map_err(|e| e.at_line(1))? })
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
-
-    let mut game = CrabCups::parse(&contents);
-    //println!("{:?}", game);
-    for _i in 0..100 {
-        game.next();
+
+    fn part1(&self) -> Answer {
+        part1(&self.game, PART1_MOVES).into()
+    }
+
+    fn part2(&self) -> Answer {
+        part2(&self.game, PART2_CUPS, PART2_MOVES).into()
     }
-    println!("Part 1: {}", game.answer());
-    
-    0
-}
\ No newline at end of file
+}
@@ -1,5 +1,7 @@
-use std::fs;
+use std::fmt;
 use itertools::iproduct;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Direction {
@@ -17,8 +19,18 @@ pub enum Color {
     Black,
 }
 
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Color::White => '.',
+            Color::Black => '#',
+        };
+        write!(f, "{}", c)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct DirectionSequence {
+pub struct DirectionSequence {
     input: Vec<char>,
     pos: usize,
 }
@@ -27,47 +39,52 @@ impl DirectionSequence {
    
     pub fn new(directions: &str) -> DirectionSequence {
         let pos = 0;
-        let input = directions.to_string().chars().collect();
+        let input = directions.chars().collect();
         DirectionSequence { input, pos }
     }
 
 }
 
 impl Iterator for DirectionSequence {
-    type Item = Direction;
+    type Item = Result<Direction, AocError>;
     
-    fn next(&mut self) -> Option<Direction> {
+    fn next(&mut self) -> Option<Result<Direction, AocError>> {
         if self.pos >= self.input.len() {
             return None;
         }
+        let column = self.pos + 1;
         self.pos += 1;
-        match self.input[self.pos-1] {
-            'e' => return Some(Direction::East),
-            'w' => return Some(Direction::West),
+        let direction = match self.input[self.pos-1] {
+            'e' => Some(Direction::East),
+            'w' => Some(Direction::West),
             'n' => {
                 self.pos += 1;
-                match self.input[self.pos-1] {
-                    'e' => return Some(Direction::NorthEast),
-                    'w' => return Some(Direction::NorthWest),
-                    _ => (),
-                };
+                match self.input.get(self.pos-1) {
+                    Some('e') => Some(Direction::NorthEast),
+                    Some('w') => Some(Direction::NorthWest),
+                    _ => None,
+                }
             },
             's' => {
                 self.pos += 1;
-                match self.input[self.pos-1] {
-                    'e' => return Some(Direction::SouthEast),
-                    'w' => return Some(Direction::SouthWest),
-                    _ => (),
-                };
+                match self.input.get(self.pos-1) {
+                    Some('e') => Some(Direction::SouthEast),
+                    Some('w') => Some(Direction::SouthWest),
+                    _ => None,
+                }
             },
-            _ => (),
+            _ => None,
         };
-        unreachable!();
+        // stop at the first bad direction
+        if direction.is_none() {
+            self.pos = self.input.len();
+        }
+        Some(direction.ok_or_else(|| AocError::parse("expected `e`, `se`, `sw`, `w`, `nw` or `ne`").at_column(column)))
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct HexGrid {
+pub struct HexGrid {
     map: Vec<Vec<Color>>,
     size: usize,
 }
@@ -108,9 +125,9 @@ impl HexGrid {
     }
 
     pub fn adjacent(&self, tile: (usize, usize)) -> Vec<Option<Color>> {
-        let dirs = vec![Direction::East, Direction::West, Direction::NorthEast,
-                        Direction::NorthWest, Direction::SouthEast, Direction::SouthWest];
-        dirs.into_iter().map(|d| self.get_tile(tile, d)).collect()
+        let dirs = [Direction::East, Direction::West, Direction::NorthEast,
+                    Direction::NorthWest, Direction::SouthEast, Direction::SouthWest];
+        dirs.iter().map(|d| self.get_tile(tile, d.clone())).collect()
     }
 
     pub fn update(&mut self, sequence: Vec<Direction>) {
@@ -156,30 +173,59 @@ impl HexGrid {
 
 }
 
-pub fn day24(args: &[String]) -> i32 {
-    println!("Day 24");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
+// Renders the smallest box of rows and columns holding every black tile. Each
+// row is shifted half a tile right of the one above, as south east is (+1, 0).
+impl fmt::Display for HexGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let black: Vec<(usize, usize)> = iproduct!(0..self.size, 0..self.size)
+            .filter(|&(y, x)| self.map[y][x] == Color::Black)
+            .collect();
+        let rows = match (black.iter().map(|t| t.0).min(), black.iter().map(|t| t.0).max()) {
+            (Some(min), Some(max)) => min..max+1,
+            _ => return Ok(()),
+        };
+        let (x_min, x_max) = (black.iter().map(|t| t.1).min().unwrap(), black.iter().map(|t| t.1).max().unwrap());
+        let mut output = Vec::new();
+        for y in rows.clone() {
+            let row: Vec<String> = (x_min..x_max+1).map(|x| self.map[y][x].to_string()).collect();
+            output.push(format!("{}{}\n", " ".repeat(y - rows.start), row.join(" ")));
+        }
+        write!(f, "{}", output.join(""))
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
-    
-    let sequences: Vec<Vec<_>>  = contents.lines().map(|l| DirectionSequence::new(l).into_iter().collect()).collect(); 
-    //println!("{:?}", sequences);
-    let mut hg = HexGrid::new(150);
-    for s in sequences {
-        hg.update(s);
-    }
-    println!("Part 1: {}", hg.count(Color::Black));
-    let mut count = 0;
-    for _d in 1..101 {
-        count = hg.next_day()
-        //println!("Day {} {:?}", d, count);
-    }
-    println!("Part 2: {}", count);
-
-    0
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone)]
+pub struct Day24 {
+    sequences: Vec<Vec<Direction>>,
+}
+
+impl Day24 {
+
+    fn initial_grid(&self) -> HexGrid {
+        let mut hg = HexGrid::new(150);
+        for s in &self.sequences {
+            hg.update(s.clone());
+        }
+        hg
+    }
+}
+
+impl Solution for Day24 {
+
+    fn parse(contents: &str) -> Result<Day24, AocError> {
+        Ok(Day24 { sequences: error::parse_lines(contents, |l| DirectionSequence::new(l).collect())? })
+    }
+
+    fn part1(&self) -> Answer {
+        self.initial_grid().count(Color::Black).into()
+    }
+
+    fn part2(&self) -> Answer {
+        let mut hg = self.initial_grid();
+        let mut count = 0;
+        for _d in 1..101 {
+            count = hg.next_day();
+        }
+        count.into()
+    }
+}
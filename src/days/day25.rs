@@ -0,0 +1,49 @@
+use crate::days::{Answer, Solution};
+use crate::days::utils;
+use crate::error::{self, AocError};
+
+pub const SUBJECT: u64 = 7;
+pub const MODULUS: u64 = 20201227;
+
+// Apply the handshake transform `loop_size` times to the subject number
+pub fn transform(subject: u64, loop_size: u64) -> u64 {
+    utils::mod_pow(subject, loop_size, MODULUS)
+}
+
+// Recover the secret loop size from a public key
+pub fn loop_size(public_key: u64) -> Option<u64> {
+    utils::discrete_log(SUBJECT, public_key, MODULUS)
+}
+
+pub fn encryption_key(card_key: u64, door_key: u64) -> Option<u64> {
+    Some(transform(door_key, loop_size(card_key)?))
+}
+
+#[derive(Debug, Clone)]
+pub struct Day25 {
+    card_key: u64,
+    door_key: u64,
+}
+
+impl Solution for Day25 {
+
+    fn parse(contents: &str) -> Result<Day25, AocError> {
+        let keys: Vec<u64> = error::parse_lines(contents, error::parse_number)?;
+        if keys.len() != 2 {
+            return Err(AocError::parse("expected the card and door public keys"));
+        }
+        if let Some(i) = keys.iter().position(|&k| k == 0 || k >= MODULUS) {
+            return Err(AocError::parse(format!("public key must be between 1 and {}", MODULUS - 1)).at_line(i + 1));
+        }
+        Ok(Day25 { card_key: keys[0], door_key: keys[1] })
+    }
+
+    fn part1(&self) -> Answer {
+        encryption_key(self.card_key, self.door_key).into()
+    }
+
+    fn part2(&self) -> Answer {
+        // day 25 only has one puzzle
+        Answer::Unsolved
+    }
+}
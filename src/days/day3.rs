@@ -1,4 +1,5 @@
-use std::fs;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Clone)]
 pub struct Geology {
@@ -7,13 +8,28 @@ pub struct Geology {
 
 impl Geology {
 
-    pub fn new(input: &String) -> Geology {
+    pub fn new(input: &str) -> Geology {
         let map: Vec<Vec<char>> = input.lines()
                     .map(|l| l.chars().collect())
                     .collect();
         Geology { map }
     }
 
+    // like new, but rejecting anything other than open squares and trees, or
+    // rows of different widths
+    pub fn parse(input: &str) -> Result<Geology, AocError> {
+        let mut width = None;
+        error::parse_lines(input, |l| match l.find(|c| c != '.' && c != '#') {
+            Some(i) => Err(AocError::parse("expected `.` or `#`").at_column(i + 1)),
+            None if l.is_empty() => Err(AocError::parse("empty row")),
+            None => error::same_width(&mut width, l.len()),
+        })?;
+        if input.lines().next().is_none() {
+            return Err(AocError::parse("empty map"));
+        }
+        Ok(Geology::new(input))
+    }
+
     pub fn width(&self) -> usize {
         self.map[0].len()
     }
@@ -23,10 +39,7 @@ impl Geology {
     }
 
     pub fn is_tree(&self, y: usize, x: usize) -> bool {
-        match self.map[y][x % self.width()] {
-            '#' => true,
-            _ => false,
-        }
+        self.map[y][x % self.width()] == '#'
     }
 
     pub fn hit_trees(&self, ystep: usize, xstep: usize) -> usize {
@@ -39,31 +52,32 @@ impl Geology {
 
 }
 
-pub fn day3(args: &[String]) -> i32 {
-    println!("Day 3");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
+#[derive(Debug, Clone)]
+pub struct Day3 {
+    geo: Geology,
+}
+
+impl Solution for Day3 {
+
+    fn parse(contents: &str) -> Result<Day3, AocError> {
+        Ok(Day3 { geo: Geology::parse(contents)? })
+    }
+
+    fn part1(&self) -> Answer {
+        self.geo.hit_trees(1, 3).into()
+    }
+
+    fn part2(&self) -> Answer {
+        let slopes = [
+            (1, 1),
+            (3, 1),
+            (5, 1),
+            (7, 1),
+            (1, 2),
+        ];
+        slopes.iter()
+              .map(|&(x, y)| self.geo.hit_trees(y, x))
+              .product::<usize>()
+              .into()
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
-    
-    let geo = Geology::new(&contents);
-    
-    println!("Part 1: {:}", geo.hit_trees(1, 3));
-    // part 2
-    let slopes = vec![
-        (1, 1),
-        (3, 1),
-        (5, 1),
-        (7, 1),
-        (1, 2),
-    ];
-    let result = slopes.iter()
-            .map(|&(x, y)| geo.hit_trees(y, x))
-            .product::<usize>();
-    println!("Part 2: {:}", result);
-    0
 }
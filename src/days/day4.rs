@@ -1,13 +1,18 @@
 use std::fs;
 use regex::{Regex, Captures};
 use std::fmt;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
-pub fn get_passport_lines(filename: &String) -> Vec<String> {
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
+pub fn get_passport_lines(filename: &str) -> Result<Vec<String>, AocError> {
+    let contents = fs::read_to_string(filename)?;
+    Ok(passport_lines(&contents))
+}
+
+pub fn passport_lines(contents: &str) -> Vec<String> {
     // normalize the input by replacing only the first occurance of an endline with a space
     let re = Regex::new(r"([^\n])\n").unwrap();
-    re.replace_all(&contents, |caps: &Captures| {
+    re.replace_all(contents, |caps: &Captures| {
             format!("{} ", &caps[1])
         })
         .lines()
@@ -23,15 +28,16 @@ pub struct KeyValue {
 
 impl KeyValue {
 
-    pub fn parse(text: &String) -> KeyValue {
+    pub fn parse(text: &str) -> Result<KeyValue, AocError> {
         lazy_static! {
-            static ref RE: Regex = Regex::new(r"([a-z]*):([^:]*)").unwrap();
+            static ref RE: Regex = Regex::new(r"^([a-z]+):([^:\s]+)$").unwrap();
         }
         // Split out key value (ex: "iyr:2013")
-        let caps = RE.captures(&text).unwrap();
-        let key = caps.get(1).unwrap().as_str().to_string();
-        let value = caps.get(2).unwrap().as_str().to_string();
-        KeyValue { key, value }
+        let caps = RE.captures(text)
+                     .ok_or_else(|| AocError::parse(format!("expected `key:value`, found `{}`", text)))?;
+        let key = caps[1].to_string();
+        let value = caps[2].to_string();
+        Ok(KeyValue { key, value })
     }
 
     pub fn is_valid(&self) -> bool {
@@ -44,19 +50,19 @@ impl KeyValue {
         match self.key.as_str() {
             "byr" => {
                 match self.value.parse::<usize>() {
-                    Ok(n) => (n >= 1920 && n <= 2002),
+                    Ok(n) => (1920..=2002).contains(&n),
                     Err(_) => false
                 }
             },
             "iyr" => {
                 match self.value.parse::<usize>() {
-                    Ok(n) => (n >= 2010 && n <= 2020),
+                    Ok(n) => (2010..=2020).contains(&n),
                     Err(_) => false
                 }
             },
             "eyr" => {
                 match self.value.parse::<usize>() {
-                    Ok(n) => (n >= 2020 && n <= 2030),
+                    Ok(n) => (2020..=2030).contains(&n),
                     Err(_) => false
                 }
             },
@@ -90,8 +96,8 @@ impl KeyValue {
         match value.parse::<usize>() {
             Ok(n) => {
                 match units {
-                    "cm" => (n >= 150 && n <= 193),
-                    "in" => (n >= 59 && n <= 76),
+                    "cm" => (150..=193).contains(&n),
+                    "in" => (59..=76).contains(&n),
                     _ => false,
                 }
             },
@@ -113,10 +119,10 @@ pub struct Passport {
 
 impl Passport {
 
-    pub fn parse(line: &String) -> Passport {
-        Passport {
-            entries: line.split(' ').map(|kv| KeyValue::parse(&kv.to_string())).collect()
-        }
+    pub fn parse(line: &str) -> Result<Passport, AocError> {
+        Ok(Passport {
+            entries: line.split_whitespace().map(KeyValue::parse).collect::<Result<_, _>>()?
+        })
     }
 
     pub fn keys(&self) -> Vec<String> {
@@ -160,25 +166,24 @@ impl Passport {
 }
 
 
-pub fn day4(args: &[String]) -> i32 {
-    println!("Day 4");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
+#[derive(Debug, Clone)]
+pub struct Day4 {
+    passports: Vec<Passport>,
+}
+
+impl Solution for Day4 {
+
+    fn parse(contents: &str) -> Result<Day4, AocError> {
+        // passports span several lines, so errors point at the passport's first line
+        let passports = error::parse_groups(contents, |g| Passport::parse(g).map_err(|e| e.at_line(1)))?;
+        Ok(Day4 { passports })
+    }
+
+    fn part1(&self) -> Answer {
+        self.passports.iter().filter(|p| p.north_pole_keys_present()).count().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.passports.iter().filter(|p| p.is_valid()).count().into()
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    
-    let lines = get_passport_lines(filename);
-    let count = lines.iter()
-                    .filter(|p| Passport::parse(&p).north_pole_keys_present())
-                    .count();
-    println!("Part 1: {}", count);
-
-    let count = lines.iter()
-                    .filter(|p| Passport::parse(&p).is_valid())
-                    .count();
-    println!("Part 2: {}", count);
-
-    0
 }
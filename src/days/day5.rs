@@ -1,5 +1,6 @@
-use std::fs;
 use regex::Regex;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Seat {
@@ -8,27 +9,26 @@ pub struct Seat {
 
 impl Seat {
 
-    pub fn parse(line: &str) -> Seat {
+    pub fn parse(line: &str) -> Result<Seat, AocError> {
         lazy_static! {
-            static ref SEAT: Regex = Regex::new(r"^[FB]{7}[LR]{3}").unwrap();
+            static ref SEAT: Regex = Regex::new(r"^[FB]{7}[LR]{3}$").unwrap();
+        }
+        if !SEAT.is_match(line) {
+            return Err(AocError::parse("expected 7 of `F`/`B` followed by 3 of `L`/`R`"));
         }
-        assert!(SEAT.is_match(&line));
         let mut id = 0;
-        let bits: Vec<bool> = line.chars().map(|c| Seat::mapping(c)).collect();
+        let bits: Vec<bool> = line.chars().map(Seat::mapping).collect();
         for b in bits {
             id <<= 1;
             if b {
                 id |= 1
             }
         }
-        Seat { id }
+        Ok(Seat { id })
     }
 
     fn mapping(c: char) -> bool {
-        match c {
-            'B' | 'R' => true,
-            _ => false
-        }
+        matches!(c, 'B' | 'R')
     }
 
     pub fn id(&self) -> usize {
@@ -37,34 +37,31 @@ impl Seat {
 
 }
 
-pub fn day5(args: &[String]) -> i32 {
-    println!("Day 5");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
+#[derive(Debug, Clone)]
+pub struct Day5 {
+    ids: Vec<usize>,
+}
+
+impl Solution for Day5 {
+
+    fn parse(contents: &str) -> Result<Day5, AocError> {
+        let mut ids = error::parse_lines(contents, |l| Ok(Seat::parse(l)?.id()))?;
+        ids.sort_unstable();
+        Ok(Day5 { ids })
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
 
-    let mut ids: Vec<usize> = contents.lines().map(|l| Seat::parse(l).id()).collect();
-    ids.sort();
-    println!("Part 1: {}", ids.last().unwrap());
-    
-    // Find the open seat between the min and max seat IDs
-    let mut i = *ids.first().unwrap();
-    for id in &ids {
-        while i < *id {
-            println!("Part 2: {}", i);
-            i += 1;
-        }
-        i += 1;
+    fn part1(&self) -> Answer {
+        self.ids.last().copied().into()
+    }
+
+    fn part2(&self) -> Answer {
+        // a single missing seat can be found by summing all the available
+        // seat IDs between the min and max and subtracting the filled seats.
+        let (first, last) = match (self.ids.first(), self.ids.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return Answer::Unsolved,
+        };
+        let rsum: usize = (first..=last).sum();
+        (rsum - self.ids.iter().sum::<usize>()).into()
     }
-    // a single missing seat can also be found by summing 
-    // the all available seat IDs and substract the sum of filled seats.
-    let rsum: usize = (*ids.first().unwrap()..*ids.last().unwrap()+1).sum(); 
-    println!("Part 2: {:?}", rsum - ids.into_iter().sum::<usize>());
-    0
 }
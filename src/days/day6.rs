@@ -1,13 +1,18 @@
 use std::fs;
 use itertools::Itertools;
 use regex::{Regex, Captures};
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
-pub fn get_answer_lines(filename: &String) -> Vec<String> {
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
+pub fn get_answer_lines(filename: &str) -> Result<Vec<String>, AocError> {
+    let contents = fs::read_to_string(filename)?;
+    Ok(answer_lines(&contents))
+}
+
+pub fn answer_lines(contents: &str) -> Vec<String> {
     // normalize the input by replacing only the first occurance of an endline with a space
     let re = Regex::new(r"([^\n])\n").unwrap();
-    re.replace_all(&contents, |caps: &Captures| {
+    re.replace_all(contents, |caps: &Captures| {
             format!("{} ", &caps[1])
         })
         .lines()
@@ -15,32 +20,35 @@ pub fn get_answer_lines(filename: &String) -> Vec<String> {
         .collect()
 }
 
-pub fn day6(args: &[String]) -> i32 {
-    println!("Day 6");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
+#[derive(Debug, Clone)]
+pub struct Day6 {
+    answer_lines: Vec<String>,
+}
+
+impl Solution for Day6 {
+
+    fn parse(contents: &str) -> Result<Day6, AocError> {
+        error::parse_lines(contents, |l| match l.find(|c: char| !c.is_ascii_lowercase()) {
+            Some(i) => Err(AocError::parse("expected answers `a` to `z`").at_column(i + 1)),
+            None => Ok(()),
+        })?;
+        Ok(Day6 { answer_lines: answer_lines(contents) })
+    }
+
+    fn part1(&self) -> Answer {
+        self.answer_lines.iter().map(|a| part1_answers(a).len()).sum::<usize>().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.answer_lines.iter().map(|a| part2_answers(a).len()).sum::<usize>().into()
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    
-    let answer_lines = get_answer_lines(filename);
-
-    // Part 1
-    let unique: Vec<usize> = answer_lines.iter().map(|a| part1_answers(a).len()).collect();
-    println!("Part 1: {}", unique.iter().sum::<usize>());
-    
-    // Part 2
-    let group_ans: Vec<usize> = answer_lines.iter().map(|a| part2_answers(&a).len()).collect();
-    println!("Part 2: {:?}", group_ans.iter().sum::<usize>());
-    0
 }
 
-pub fn part1_answers(line: &String) -> Vec<char> {
-    line.replace(" ", "").chars().unique().collect()
+pub fn part1_answers(line: &str) -> Vec<char> {
+    line.replace(' ', "").chars().unique().collect()
 }
 
-pub fn part2_answers(line: &String) -> Vec<char> {
+pub fn part2_answers(line: &str) -> Vec<char> {
     static ALPHA: &str = "abcdefghijklmnopqrstuvwxyz";
     let mut result = vec![true; 26];
     for a in line.split(' ') {
@@ -1,5 +1,6 @@
-use std::fs;
 use regex::Regex;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Bag {
@@ -18,12 +19,12 @@ impl Bag {
         lazy_static! {
             static ref BAG: Regex = Regex::new(r"^(\d+) (\w+ \w+) bags?\.?$").unwrap();
         }
-        if !BAG.is_match(&text) {
+        if !BAG.is_match(text) {
             return None;
         }
         let caps = BAG.captures(text).unwrap();
-        let count = caps.get(1).unwrap().as_str().parse::<usize>().unwrap();
-        let color_code = caps.get(2).unwrap().as_str().to_string();
+        let count = caps[1].parse::<usize>().ok()?;
+        let color_code = caps[2].to_string();
         Some(Bag{ color_code, count })
     }
 
@@ -41,53 +42,54 @@ impl Rule {
         Rule { outer, inner }
     }
 
-    pub fn parse(line: &str) -> Rule {
+    pub fn parse(line: &str) -> Result<Rule, AocError> {
         let p: Vec<&str> = line.split(" contain ").collect();
-        assert_eq!(p.len(), 2);
-        Rule { 
-            outer: Bag::parse(&format!("1 {}", p[0])).unwrap(), // Workaround to add a '1' count to the outer bag
-            inner: p[1].split(", ")
-                       .map(|t| Bag::parse(t))
-                       .filter(|x| x.is_some())
-                       .map(|x| x.unwrap())
-                       .collect()
+        if p.len() != 2 {
+            return Err(AocError::parse("expected `<color> bags contain <bags>`"));
         }
+        let outer = Bag::parse(&format!("1 {}", p[0])) // Workaround to add a '1' count to the outer bag
+                        .ok_or_else(|| AocError::parse(format!("expected `<color> bags`, found `{}`", p[0])))?;
+        let inner = match p[1] {
+            "no other bags." => vec![],
+            bags => bags.split(", ")
+                        .map(|t| Bag::parse(t).ok_or_else(|| AocError::parse(format!("expected `<count> <color> bags`, found `{}`", t))))
+                        .collect::<Result<_, _>>()?,
+        };
+        Ok(Rule { outer, inner })
     }
 }
 
-pub fn day7(args: &[String]) -> i32 {
-    println!("Day 7");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
+#[derive(Debug, Clone)]
+pub struct Day7 {
+    rules: Vec<Rule>,
+}
 
-    let rules: Vec<Rule> = contents.lines().map(|l| Rule::parse(l)).collect();
-    println!("Rules {:?}", rules.len());
+impl Solution for Day7 {
 
-    // Part 1
-    println!("Part 1: {}", part1("shiny gold", &rules));
+    fn parse(contents: &str) -> Result<Day7, AocError> {
+        Ok(Day7 { rules: error::parse_lines(contents, Rule::parse)? })
+    }
 
-    // Part 2
-    println!("Part 2: {}", part2("shiny gold", &rules));
-    0
+    fn part1(&self) -> Answer {
+        part1("shiny gold", &self.rules).into()
+    }
+
+    fn part2(&self) -> Answer {
+        part2("shiny gold", &self.rules).into()
+    }
 }
 
-pub fn part1(color_code: &str, rules: &Vec<Rule>) -> usize {
+pub fn part1(color_code: &str, rules: &[Rule]) -> usize {
     let result: Vec<(String, bool)> = 
         rules.iter()
-             .map(|r| (r.outer.color_code.to_string(), contains_bag(color_code, &r, &rules)))
+             .map(|r| (r.outer.color_code.to_string(), contains_bag(color_code, r, rules)))
              .collect();
     // count the bags that can contain (excluding itself, the reson for the -1)
     result.iter().filter(|(_c, r)| *r).count() - 1
 }
 
-pub fn contains_bag(color_code: &str, current: &Rule, rules: &Vec<Rule>) -> bool {
-    if color_code == &current.outer.color_code {
+pub fn contains_bag(color_code: &str, current: &Rule, rules: &[Rule]) -> bool {
+    if color_code == current.outer.color_code {
         return true;
     }
     for i in &current.inner {
@@ -95,7 +97,7 @@ pub fn contains_bag(color_code: &str, current: &Rule, rules: &Vec<Rule>) -> bool
         for r in rules {
             if r.outer.color_code == i.color_code {
                 // recursively traverse tree
-                if contains_bag(&color_code, r, rules) {
+                if contains_bag(color_code, r, rules) {
                     return true;
                 }
             }
@@ -104,16 +106,15 @@ pub fn contains_bag(color_code: &str, current: &Rule, rules: &Vec<Rule>) -> bool
     false
 }
 
-pub fn part2(color_code: &str, rules: &Vec<Rule>) -> usize {
-    let r = rules.iter().find(|r| color_code == &r.outer.color_code);
-    if r.is_some() {
+pub fn part2(color_code: &str, rules: &[Rule]) -> usize {
+    if let Some(r) = rules.iter().find(|r| color_code == r.outer.color_code) {
         // count the bags (excluding itself, the reson for the -1)
-        return inner_bag_count(r.unwrap(), rules) - 1;
+        return inner_bag_count(r, rules) - 1;
     }
     0
 }
 
-pub fn inner_bag_count(current: &Rule, rules: &Vec<Rule>) -> usize {
+pub fn inner_bag_count(current: &Rule, rules: &[Rule]) -> usize {
     let mut bag_count = 1;
     for i in &current.inner {
         // find the bag rule
@@ -1,132 +1,547 @@
-use std::fs;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
 use regex::Regex;
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+pub mod analysis;
+pub mod asm;
+pub mod debugger;
+pub mod trace;
+
+use trace::{Trace, TraceEntry};
+
+// Declares `Opcode` with its mnemonics, so a new instruction is one line here
+// plus a handler registered in an `InstructionSet`
+macro_rules! opcodes {
+    ($($opcode:ident => $mnemonic:literal,)*) => {
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum Opcode {
+            $($opcode,)*
+        }
+
+        impl Opcode {
+
+            pub const ALL: &'static [Opcode] = &[$(Opcode::$opcode,)*];
+
+            pub fn mnemonic(&self) -> &'static str {
+                match self {
+                    $(Opcode::$opcode => $mnemonic,)*
+                }
+            }
+        }
+    };
+}
+
+opcodes! {
+    Nop => "nop",
+    Acc => "acc",
+    Jmp => "jmp",
+    Mul => "mul",
+    Jz => "jz",
+    Out => "out",
+    Hlt => "hlt",
+}
+
+impl Opcode {
+
+    // The opcode a corrupted instruction may have been swapped with
+    pub fn flipped(&self) -> Option<Opcode> {
+        match self {
+            Opcode::Nop => Some(Opcode::Jmp),
+            Opcode::Jmp => Some(Opcode::Nop),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Opcode {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Opcode, AocError> {
+        Opcode::ALL.iter()
+                   .find(|o| o.mnemonic() == s)
+                   .copied()
+                   .ok_or_else(|| AocError::parse(format!("unknown opcode `{}`", s)))
+    }
+}
+
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.mnemonic())
+    }
+}
+
+pub const REGISTERS: usize = 8;
+
+// A register an instruction operates on: the accumulator `acc`, or `r1` to `r7`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Register(usize);
+
+impl Register {
+
+    pub const ACC: Register = Register(0);
+
+    pub fn new(index: usize) -> Option<Register> {
+        if index < REGISTERS { Some(Register(index)) } else { None }
+    }
+
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+impl FromStr for Register {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Register, AocError> {
+        match s {
+            "acc" => Ok(Register::ACC),
+            _ => s.strip_prefix('r')
+                  .and_then(|n| n.parse::<usize>().ok())
+                  .filter(|&n| n > 0)
+                  .and_then(Register::new)
+                  .ok_or_else(|| AocError::parse(format!("unknown register `{}`, expected `acc` or `r1` to `r{}`", s, REGISTERS - 1))),
+        }
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            0 => write!(f, "acc"),
+            n => write!(f, "r{}", n),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Instruction {
-    operation: String,
+    opcode: Opcode,
+    // written only when it isn't `acc`, e.g. `mul r1 +2`
+    register: Register,
     argument: isize,
 }
 
 impl Instruction {
 
-    pub fn new(operation: String, argument: isize) -> Instruction {
-        Instruction { operation, argument }
+    pub fn new(opcode: Opcode, argument: isize) -> Instruction {
+        Instruction { opcode, register: Register::ACC, argument }
+    }
+
+    pub fn with_register(opcode: Opcode, register: Register, argument: isize) -> Instruction {
+        Instruction { opcode, register, argument }
     }
 
-    pub fn parse(line: &str) -> Instruction {
-        // ex "acc +6"
+    pub fn parse(line: &str) -> Result<Instruction, AocError> {
+        // ex "acc +6" or "mul r1 -2"
         lazy_static! {
-            static ref INSTR: Regex = Regex::new(r"^(nop|acc|jmp) ([\-\+]\d+)$").unwrap();
+            static ref INSTR: Regex = Regex::new(r"^([a-z]+) (?:([a-z][a-z0-9]*) )?([\-\+]\d+)$").unwrap();
+        }
+        let caps = INSTR.captures(line)
+                        .ok_or_else(|| AocError::parse("expected an opcode and a signed argument, e.g. `acc +6`"))?;
+        let opcode = caps[1].parse::<Opcode>()?;
+        let register = match caps.get(2) {
+            Some(r) => r.as_str().parse::<Register>()?,
+            None => Register::ACC,
+        };
+        let argument = caps[3].parse::<isize>()?;
+        Ok(Instruction { opcode, register, argument })
+    }
+
+    pub fn opcode(&self) -> Opcode {
+        self.opcode
+    }
+
+    pub fn register(&self) -> Register {
+        self.register
+    }
+
+    pub fn argument(&self) -> isize {
+        self.argument
+    }
+
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.register {
+            Register::ACC => write!(f, "{} {:+}", self.opcode, self.argument),
+            r => write!(f, "{} {} {:+}", self.opcode, r, self.argument),
         }
-        assert!(INSTR.is_match(&line));
-        let caps = INSTR.captures(line).unwrap();
-        let operation = caps.get(1).unwrap().as_str().to_string();
-        let argument = caps.get(2).unwrap().as_str().parse::<isize>().unwrap();
-        Instruction { operation, argument }
     }
+}
+
+// What the machine does after executing an instruction
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Flow {
+    Next,
+    Jump(isize),
+    Halt,
+}
+
+// The register file, indexed by `Register`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Registers {
+    values: [isize; REGISTERS],
+    // values written by `out`
+    pub output: Vec<isize>,
+}
+
+impl Registers {
+
+    pub fn acc(&self) -> isize {
+        self[Register::ACC]
+    }
+}
+
+impl Index<Register> for Registers {
+    type Output = isize;
+
+    fn index(&self, r: Register) -> &isize {
+        &self.values[r.0]
+    }
+}
+
+impl IndexMut<Register> for Registers {
+    fn index_mut(&mut self, r: Register) -> &mut isize {
+        &mut self.values[r.0]
+    }
+}
+
+// Executes one instruction with its register and argument
+pub type Handler = fn(&mut Registers, Register, isize) -> Flow;
+
+// How a program decides it is stuck in a loop
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LoopDetection {
+    // about to run any instruction for the second time, which is only certain
+    // to repeat forever when jumps don't depend on the registers
+    Pc,
+    // about to run an instruction with exactly the registers it had before. A
+    // loop that keeps changing a register isn't caught and runs forever.
+    State,
+}
 
+// Maps each supported opcode to its behaviour. The run loop only goes through
+// this table, so new instructions are added by registering a handler, which
+// is free to use the instruction's register.
+#[derive(Clone)]
+pub struct InstructionSet {
+    handlers: HashMap<Opcode, Handler>,
+    loops: LoopDetection,
 }
 
+impl InstructionSet {
+
+    pub fn empty() -> InstructionSet {
+        InstructionSet { handlers: HashMap::new(), loops: LoopDetection::Pc }
+    }
+
+    // The handheld's original `nop`, `acc` and `jmp`
+    pub fn standard() -> InstructionSet {
+        InstructionSet::empty()
+            .with(Opcode::Nop, |_r, _reg, _a| Flow::Next)
+            .with(Opcode::Acc, |r, reg, a| { r[reg] += a; Flow::Next })
+            .with(Opcode::Jmp, |_r, _reg, a| Flow::Jump(a))
+    }
+
+    // The standard set plus `mul`, `jz` (jump if the register is zero), `out`
+    // (write the register to the output) and `hlt`. Since `jz` allows counted
+    // loops, a program only loops when it repeats its whole state.
+    pub fn extended() -> InstructionSet {
+        InstructionSet::standard()
+            .with_loop_detection(LoopDetection::State)
+            .with(Opcode::Mul, |r, reg, a| { r[reg] *= a; Flow::Next })
+            .with(Opcode::Jz, |r, reg, a| if r[reg] == 0 { Flow::Jump(a) } else { Flow::Next })
+            .with(Opcode::Out, |r, reg, _a| { let v = r[reg]; r.output.push(v); Flow::Next })
+            .with(Opcode::Hlt, |_r, _reg, _a| Flow::Halt)
+    }
+
+    pub fn with(mut self, opcode: Opcode, handler: Handler) -> InstructionSet {
+        self.handlers.insert(opcode, handler);
+        self
+    }
+
+    pub fn with_loop_detection(mut self, loops: LoopDetection) -> InstructionSet {
+        self.loops = loops;
+        self
+    }
+
+    pub fn loop_detection(&self) -> LoopDetection {
+        self.loops
+    }
+
+    pub fn get(&self, opcode: Opcode) -> Option<Handler> {
+        self.handlers.get(&opcode).copied()
+    }
+
+    pub fn supports(&self, opcode: Opcode) -> bool {
+        self.handlers.contains_key(&opcode)
+    }
+
+    pub fn opcodes(&self) -> Vec<Opcode> {
+        let mut opcodes: Vec<Opcode> = self.handlers.keys().copied().collect();
+        opcodes.sort_unstable();
+        opcodes
+    }
+}
+
+impl fmt::Debug for InstructionSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("InstructionSet")
+         .field("opcodes", &self.opcodes())
+         .field("loops", &self.loops)
+         .finish()
+    }
+}
+
+// Where a program is, or why it stopped
 #[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Running,
+    Complete,
+    // about to repeat itself, see `LoopDetection`
+    Looping,
+    Halted,
+    Fault(String),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Running => write!(f, "running"),
+            Status::Complete => write!(f, "complete"),
+            Status::Looping => write!(f, "looping"),
+            Status::Halted => write!(f, "halted"),
+            Status::Fault(e) => write!(f, "fault: {}", e),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Program {
     pc: usize,
-    acc: isize,
+    registers: Registers,
     code: Vec<Instruction>,
-    run_count: Vec<usize>
+    run_count: Vec<usize>,
+    set: InstructionSet,
+    loops: LoopDetection,
+    // (pc, registers) before each executed instruction, for `LoopDetection::State`
+    seen: HashSet<(usize, [isize; REGISTERS])>,
+    halted: bool,
+    fault: Option<String>,
+    // executed instructions, when recording
+    trace: Option<Trace>,
 }
 
 impl Program {
 
     pub fn new(code: Vec<Instruction>) -> Program {
+        Program::with_instruction_set(code, InstructionSet::standard())
+    }
+
+    pub fn with_instruction_set(code: Vec<Instruction>, set: InstructionSet) -> Program {
         let count = code.len();
-        Program { pc: 0, acc: 0, code: code, run_count: vec![0; count] }
+        let loops = set.loop_detection();
+        Program {
+            pc: 0,
+            registers: Registers::default(),
+            code,
+            run_count: vec![0; count],
+            set,
+            loops,
+            seen: HashSet::new(),
+            halted: false,
+            fault: None,
+            trace: None,
+        }
     }
 
     // run program, returning the acc result
     pub fn run(&mut self) -> isize {
-        loop {
-            self.run_count[self.pc] += 1; //increment instuction's run count
-            let i = &self.code[self.pc];
-            //println!("pc: {} - acc: {} - {:?} ", self.pc, self.acc, i);
-            match i.operation.as_str() {
-                "nop" => {
-                    self.pc += 1;
-                },
-                "acc" => {
-                    self.acc += i.argument;
-                    self.pc += 1;
-                },
-                "jmp" => {
-                    self.pc = (self.pc as isize + i.argument) as usize;
-                },
-                _ => unreachable!("Invalid instruction: {:?}", i)
-            }
-            if self.complete() || self.looping() {
-                break;
-            }
+        while !self.stopped() {
+            self.step();
+        }
+        self.registers.acc()
+    }
+
+    // Execute the instruction at pc, even if it has run before. Returns false
+    // if the program has completed, halted or faulted instead.
+    pub fn step(&mut self) -> bool {
+        if self.complete() || self.halted || self.fault.is_some() {
+            return false;
+        }
+        let i = self.code[self.pc];
+        let handler = match self.set.get(i.opcode) {
+            Some(handler) => handler,
+            None => {
+                self.fault = Some(format!("unsupported instruction `{}` at {}", i, self.pc));
+                return false;
+            },
+        };
+        self.run_count[self.pc] += 1; //increment instuction's run count
+        if self.loops == LoopDetection::State {
+            self.seen.insert((self.pc, self.registers.values));
+        }
+        let (pc, before) = (self.pc, self.registers[i.register]);
+        match handler(&mut self.registers, i.register, i.argument) {
+            Flow::Next => self.pc += 1,
+            Flow::Jump(offset) => match self.pc as isize + offset {
+                target if target < 0 => self.fault = Some(format!("jump to {} from {}", target, self.pc)),
+                target => self.pc = target as usize,
+            },
+            Flow::Halt => self.halted = true,
+        }
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry { pc, instruction: i, before, after: self.registers[i.register] });
+        }
+        true
+    }
+
+    pub fn status(&self) -> Status {
+        if let Some(fault) = &self.fault {
+            Status::Fault(fault.clone())
+        } else if self.halted {
+            Status::Halted
+        } else if self.complete() {
+            Status::Complete
+        } else if self.looping() {
+            Status::Looping
+        } else {
+            Status::Running
         }
-        self.acc
+    }
+
+    // complete, looping, halted or faulted
+    pub fn stopped(&self) -> bool {
+        self.complete() || self.looping() || self.halted || self.fault.is_some()
     }
 
     pub fn looping(&self) -> bool {
-        self.run_count[self.pc] > 0
+        !self.complete() && match self.loops {
+            LoopDetection::Pc => self.run_count[self.pc] > 0,
+            LoopDetection::State => self.seen.contains(&(self.pc, self.registers.values)),
+        }
+    }
+
+    // Override the instruction set's loop detection
+    pub fn set_loop_detection(&mut self, loops: LoopDetection) {
+        self.loops = loops;
     }
 
     pub fn complete(&self) -> bool {
         self.pc >= self.code.len()
     }
 
+    pub fn halted(&self) -> bool {
+        self.halted
+    }
+
+    pub fn fault(&self) -> Option<&str> {
+        self.fault.as_deref()
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn acc(&self) -> isize {
+        self.registers.acc()
+    }
+
+    pub fn register(&self, register: Register) -> isize {
+        self.registers[register]
+    }
+
+    pub fn output(&self) -> &[isize] {
+        &self.registers.output
+    }
+
+    pub fn code(&self) -> &[Instruction] {
+        &self.code
+    }
+
+    // How many times the instruction at index has run
+    pub fn run_count(&self, index: usize) -> usize {
+        self.run_count.get(index).copied().unwrap_or(0)
+    }
+
+    // Start recording every executed instruction
+    pub fn record(&mut self) {
+        self.trace.get_or_insert_with(Trace::new);
+    }
+
+    pub fn trace(&self) -> Option<&Trace> {
+        self.trace.as_ref()
+    }
+
+    pub fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+
     pub fn corrupt_instruction(&mut self, index: usize) {
-        let mut i = &mut self.code[index];
-        match i.operation.as_str() {
-            "nop" => {
-                i.operation = String::from("jmp")
-            },
-            "jmp" => {
-                i.operation = String::from("nop")
-            },
-            _ => unreachable!("Invalid corruption: {:?}", i)
+        let i = &mut self.code[index];
+        if let Some(opcode) = i.opcode.flipped() {
+            i.opcode = opcode;
         }
     }
 
 }
 
-pub fn day8(args: &[String]) -> i32 {
-    println!("Day 8");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
+#[derive(Debug, Clone)]
+pub struct Day8 {
+    code: Vec<Instruction>,
+}
+
+impl Solution for Day8 {
+
+    fn parse(contents: &str) -> Result<Day8, AocError> {
+        let set = InstructionSet::standard();
+        let code = error::parse_lines(contents, |l| {
+            let i = Instruction::parse(l)?;
+            if !set.supports(i.opcode) {
+                return Err(AocError::parse(format!("unsupported opcode `{}`", i.opcode)));
+            }
+            // the handheld only has the accumulator
+            if i.register != Register::ACC {
+                return Err(AocError::parse(format!("unsupported register `{}`", i.register)));
+            }
+            Ok(i)
+        })?;
+        Ok(Day8 { code })
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
 
-    let code: Vec<Instruction> = contents.lines().map(|l| Instruction::parse(l)).collect();
-    //println!("{:?}", code);
-    let mut program = Program::new(code.clone());
+    fn part1(&self) -> Answer {
+        Program::new(self.code.clone()).run().into()
+    }
 
-    println!("Part 1: {:?}", program.run());
+    fn part2(&self) -> Answer {
+        part2(Program::new(self.code.clone())).into()
+    }
+}
 
-    println!("Part 2: {:?}", part2(Program::new(code.clone())));
-    0
+pub fn part2(program: Program) -> Option<isize> {
+    analysis::repair(&program).map(|r| r.acc)
 }
 
-pub fn part2(program: Program) -> isize {
+// Flip each `jmp`/`nop` in turn and rerun the whole program, O(n^2)
+pub fn part2_brute_force(program: Program) -> Option<isize> {
     let corrupt_locations = program.code.iter().enumerate()
-                                        .filter(|(_i, c)| c.operation == "jmp" || c.operation == "nop")
+                                        .filter(|(_i, c)| c.opcode.flipped().is_some())
                                         .map(|(i, _c)| i)
                                         .collect::<Vec<usize>>();
 
     for loc in corrupt_locations {
-        //println!("Corrupting location {}", loc);
         let mut corrupt = program.clone();
         corrupt.corrupt_instruction(loc);
 
         let acc = corrupt.run();
         if corrupt.complete() {
-            return acc;
+            return Some(acc);
         }
     }
-    0
-}
\ No newline at end of file
+    None
+}
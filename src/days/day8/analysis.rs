@@ -0,0 +1,135 @@
+use std::collections::VecDeque;
+use crate::days::day8::{Instruction, LoopDetection, Opcode, Program};
+
+// The instructions control can pass to after each instruction. Node
+// `code.len()` stands for running off the end of the program, which is how it
+// terminates; jumps before the start have no successor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControlFlowGraph {
+    successors: Vec<Vec<usize>>,
+}
+
+impl ControlFlowGraph {
+
+    pub fn new(code: &[Instruction]) -> ControlFlowGraph {
+        let successors = code.iter()
+                             .enumerate()
+                             .map(|(i, &instruction)| successors(code.len(), i, instruction))
+                             .collect();
+        ControlFlowGraph { successors }
+    }
+
+    // The node for termination
+    pub fn end(&self) -> usize {
+        self.successors.len()
+    }
+
+    pub fn successors(&self, index: usize) -> &[usize] {
+        self.successors.get(index).map(|s| s.as_slice()).unwrap_or(&[])
+    }
+
+    fn predecessors(&self) -> Vec<Vec<usize>> {
+        let mut predecessors = vec![vec![]; self.end() + 1];
+        for (i, s) in self.successors.iter().enumerate() {
+            s.iter().for_each(|&to| predecessors[to].push(i));
+        }
+        predecessors
+    }
+
+    // For each instruction, whether some path from it reaches the end. Walks
+    // the reversed edges back from the end, so each edge is visited once.
+    pub fn terminating(&self) -> Vec<bool> {
+        let predecessors = self.predecessors();
+        let mut reaches = vec![false; self.end() + 1];
+        reaches[self.end()] = true;
+        let mut queue = VecDeque::from(vec![self.end()]);
+        while let Some(node) = queue.pop_front() {
+            for &p in &predecessors[node] {
+                if !reaches[p] {
+                    reaches[p] = true;
+                    queue.push_back(p);
+                }
+            }
+        }
+        reaches.pop();
+        reaches
+    }
+
+    // For each instruction, whether every path from it reaches the end, so
+    // the program terminates whichever way its conditional jumps go. Same walk
+    // as `terminating`, but an instruction only counts once all of its
+    // successors do.
+    pub fn always_terminating(&self) -> Vec<bool> {
+        let predecessors = self.predecessors();
+        let mut pending: Vec<usize> = self.successors.iter().map(|s| s.len()).collect();
+        let mut reaches = vec![false; self.end() + 1];
+        reaches[self.end()] = true;
+        let mut queue = VecDeque::from(vec![self.end()]);
+        while let Some(node) = queue.pop_front() {
+            for &p in &predecessors[node] {
+                pending[p] -= 1;
+                if pending[p] == 0 {
+                    reaches[p] = true;
+                    queue.push_back(p);
+                }
+            }
+        }
+        reaches.pop();
+        reaches
+    }
+}
+
+fn successors(len: usize, index: usize, instruction: Instruction) -> Vec<usize> {
+    let next = index + 1;
+    // anywhere past the last instruction terminates
+    let jump = match index as isize + instruction.argument() {
+        target if target < 0 => None,
+        target => Some((target as usize).min(len)),
+    };
+    match instruction.opcode() {
+        Opcode::Nop | Opcode::Acc | Opcode::Mul | Opcode::Out => vec![next],
+        Opcode::Jmp => jump.into_iter().collect(),
+        Opcode::Jz => Some(next).into_iter().chain(jump).collect(),
+        Opcode::Hlt => vec![],
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Repair {
+    // the instruction to flip
+    pub index: usize,
+    // the accumulator once the repaired program terminates
+    pub acc: isize,
+}
+
+// Find the `jmp`/`nop` flip that lets a looping program terminate. Only
+// instructions the program actually runs can matter, and flipping one of them
+// fixes the program if every path from its new successor reaches the end,
+// which the control-flow graph answers for every instruction at once. Each
+// candidate is confirmed by running the repaired program. None if the program
+// already terminates or no single flip helps.
+pub fn repair(program: &Program) -> Option<Repair> {
+    // only the instructions run before the first repeat are candidates, and
+    // stopping there keeps a loop that changes registers from running forever
+    let mut original = program.clone();
+    original.set_loop_detection(LoopDetection::Pc);
+    original.record();
+    original.run();
+    if original.complete() {
+        return None;
+    }
+    let terminating = ControlFlowGraph::new(program.code()).always_terminating();
+    let len = program.code().len();
+    original.trace()?.entries().iter().map(|e| e.pc).filter(|&pc| {
+        let i = program.code()[pc];
+        i.opcode().flipped()
+         .map(|opcode| successors(len, pc, Instruction::with_register(opcode, i.register(), i.argument())))
+         .is_some_and(|s| s.iter().all(|&to| to == len || terminating[to]))
+    }).find_map(|index| {
+        let mut repaired = program.clone();
+        repaired.set_loop_detection(LoopDetection::Pc);
+        repaired.corrupt_instruction(index);
+        let acc = repaired.run();
+        if repaired.complete() { Some(Repair { index, acc }) } else { None }
+    })
+}
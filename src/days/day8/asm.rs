@@ -0,0 +1,125 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use regex::Regex;
+use crate::days::day8::{Instruction, Opcode, Register};
+use crate::error::AocError;
+
+// Boot code assembly: the raw `acc +6` form, plus
+//
+//     ; comments, with `;` or `#`, to the end of the line
+//     loop_start:         a label for the next instruction
+//         acc +1
+//         mul r1 +2       an optional register, `acc` if left out
+//         jmp loop_start  a jump to a label, lowered to a relative offset
+//
+// Labels may also share a line with their instruction (`top: nop +0`), and a
+// label after the last instruction marks the end of the program.
+
+#[derive(Debug, Clone)]
+enum Argument {
+    Offset(isize),
+    Label(String),
+}
+
+// Opcodes whose argument is a jump offset. `nop` counts since repairing the
+// program may turn it into a `jmp`.
+fn takes_target(opcode: Opcode) -> bool {
+    matches!(opcode, Opcode::Jmp | Opcode::Jz | Opcode::Nop)
+}
+
+fn strip_comment(line: &str) -> &str {
+    match line.find([';', '#']) {
+        Some(i) => &line[..i],
+        None => line,
+    }
+}
+
+fn parse_instruction(s: &str) -> Result<(Opcode, Register, Argument), AocError> {
+    lazy_static! {
+        static ref INSTR: Regex = Regex::new(r"^([a-z]+)\s+(?:(acc|r\d+)\s+)?(?:([\-\+]?\d+)|([A-Za-z_][A-Za-z0-9_]*))$").unwrap();
+    }
+    let caps = INSTR.captures(s)
+                    .ok_or_else(|| AocError::parse(format!("expected an instruction, e.g. `acc +6` or `jmp loop`, found `{}`", s)))?;
+    let opcode = caps[1].parse::<Opcode>()?;
+    let register = match caps.get(2) {
+        Some(r) => r.as_str().parse::<Register>()?,
+        None => Register::ACC,
+    };
+    match caps.get(3) {
+        Some(n) => Ok((opcode, register, Argument::Offset(n.as_str().parse()?))),
+        None if takes_target(opcode) => Ok((opcode, register, Argument::Label(caps[4].to_string()))),
+        None => Err(AocError::parse(format!("`{}` takes a number, not label `{}`", opcode, &caps[4]))),
+    }
+}
+
+// Lower assembly to instructions, resolving labels to relative offsets
+pub fn assemble(source: &str) -> Result<Vec<Instruction>, AocError> {
+    lazy_static! {
+        static ref LABEL: Regex = Regex::new(r"^([A-Za-z_][A-Za-z0-9_]*):\s*(.*)$").unwrap();
+    }
+    let mut labels: HashMap<String, usize> = HashMap::new();
+    // (line number, opcode, register, argument)
+    let mut pending = Vec::new();
+    for (i, line) in source.lines().enumerate() {
+        let mut rest = strip_comment(line).trim();
+        if let Some(caps) = LABEL.captures(rest) {
+            let label = caps.get(1).unwrap().as_str();
+            if labels.insert(label.to_string(), pending.len()).is_some() {
+                return Err(AocError::parse(format!("duplicate label `{}`", label)).at_line(i + 1));
+            }
+            rest = caps.get(2).unwrap().as_str();
+        }
+        if !rest.is_empty() {
+            let (opcode, register, argument) = parse_instruction(rest).map_err(|e| e.at_line(i + 1))?;
+            pending.push((i + 1, opcode, register, argument));
+        }
+    }
+    pending.into_iter()
+           .enumerate()
+           .map(|(index, (line, opcode, register, argument))| match argument {
+               Argument::Offset(offset) => Ok(Instruction::with_register(opcode, register, offset)),
+               Argument::Label(label) => match labels.get(&label) {
+                   Some(&target) => Ok(Instruction::with_register(opcode, register, target as isize - index as isize)),
+                   None => Err(AocError::parse(format!("undefined label `{}`", label)).at_line(line)),
+               },
+           })
+           .collect()
+}
+
+// Render instructions as assembly. Every `jmp` and `jz` landing inside the
+// program (or just past its end) gets a generated label, numbered in program
+// order, and is annotated with its raw offset and target. Assembling the
+// result gives back the same instructions.
+pub fn disassemble(code: &[Instruction]) -> String {
+    let target = |index: usize, i: &Instruction| match index as isize + i.argument {
+        t if matches!(i.opcode, Opcode::Jmp | Opcode::Jz) && t >= 0 && t as usize <= code.len() => Some(t as usize),
+        _ => None,
+    };
+    let labels: BTreeMap<usize, String> = code.iter()
+                                              .enumerate()
+                                              .filter_map(|(index, i)| target(index, i))
+                                              .collect::<BTreeSet<usize>>()
+                                              .into_iter()
+                                              .enumerate()
+                                              .map(|(n, t)| (t, format!("L{}", n + 1)))
+                                              .collect();
+    let mut lines = Vec::new();
+    for (index, i) in code.iter().enumerate() {
+        if let Some(label) = labels.get(&index) {
+            lines.push(format!("{}:", label));
+        }
+        match target(index, i) {
+            Some(t) => {
+                let instruction = match i.register {
+                    Register::ACC => format!("{} {}", i.opcode, labels[&t]),
+                    r => format!("{} {} {}", i.opcode, r, labels[&t]),
+                };
+                lines.push(format!("    {:<16}; {:+} -> {}", instruction, i.argument, t));
+            },
+            None => lines.push(format!("    {}", i)),
+        }
+    }
+    if let Some(label) = labels.get(&code.len()) {
+        lines.push(format!("{}:", label));
+    }
+    lines.iter().map(|l| format!("{}\n", l)).collect()
+}
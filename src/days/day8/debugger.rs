@@ -0,0 +1,357 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+use crate::days::day8::{Program, Register, Status};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+
+    pub fn test(&self, value: isize, against: isize) -> bool {
+        match self {
+            Comparison::Eq => value == against,
+            Comparison::Ne => value != against,
+            Comparison::Lt => value < against,
+            Comparison::Le => value <= against,
+            Comparison::Gt => value > against,
+            Comparison::Ge => value >= against,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Eq => "==",
+            Comparison::Ne => "!=",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+        }
+    }
+}
+
+impl FromStr for Comparison {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Comparison, String> {
+        [Comparison::Eq, Comparison::Ne, Comparison::Lt, Comparison::Le, Comparison::Gt, Comparison::Ge]
+            .iter()
+            .find(|c| c.symbol() == s)
+            .copied()
+            .ok_or_else(|| format!("Unknown comparison: {}", s))
+    }
+}
+
+// Breakpoints stop before an instruction runs, watchpoints after the
+// instruction that made their condition true
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Breakpoint {
+    Pc(usize),
+    // the nth (1-based) execution of the instruction at pc
+    Nth { pc: usize, n: usize },
+    Acc(Comparison, isize),
+    AccChanged,
+}
+
+impl Breakpoint {
+
+    fn is_watchpoint(&self) -> bool {
+        matches!(self, Breakpoint::Acc(..) | Breakpoint::AccChanged)
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Breakpoint::Pc(pc) => write!(f, "break at {}", pc),
+            Breakpoint::Nth { pc, n } => write!(f, "break at {} on execution {}", pc, n),
+            Breakpoint::Acc(c, v) => write!(f, "watch acc {} {}", c.symbol(), v),
+            Breakpoint::AccChanged => write!(f, "watch acc changes"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    // executed the requested number of steps
+    Stepped,
+    // stopped at the breakpoint with this id
+    Break(usize),
+    Stopped(Status),
+}
+
+pub struct Debugger {
+    program: Program,
+    breakpoints: Vec<Option<Breakpoint>>,
+    history: VecDeque<usize>,
+    history_len: usize,
+    // the pc of the breakpoint we're stopped at, which `resume` runs past
+    last_break: Option<usize>,
+    // the pcs the program keeps repeating, once it has started to loop
+    cycle: Option<Vec<usize>>,
+    // treat running an instruction twice as the end of the program, unless
+    // an nth-execution breakpoint inside the loop has yet to fire
+    pub stop_on_loop: bool,
+}
+
+impl Debugger {
+
+    pub fn new(program: Program) -> Debugger {
+        Debugger { program, breakpoints: vec![], history: VecDeque::new(), history_len: 32, last_break: None, cycle: None, stop_on_loop: true }
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    // Returns the id of the new breakpoint
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> usize {
+        self.breakpoints.push(Some(breakpoint));
+        self.breakpoints.len()
+    }
+
+    pub fn remove_breakpoint(&mut self, id: usize) -> bool {
+        match self.breakpoints.get_mut(id.wrapping_sub(1)) {
+            Some(b) => b.take().is_some(),
+            None => false,
+        }
+    }
+
+    // (id, breakpoint) pairs of the active breakpoints
+    pub fn breakpoints(&self) -> Vec<(usize, Breakpoint)> {
+        self.breakpoints.iter()
+                        .enumerate()
+                        .filter_map(|(i, b)| b.map(|b| (i + 1, b)))
+                        .collect()
+    }
+
+    // The most recently executed pcs, oldest first
+    pub fn backtrace(&self) -> Vec<usize> {
+        self.history.iter().copied().collect()
+    }
+
+    fn stopped(&self) -> Option<Status> {
+        match self.program.status() {
+            Status::Running => None,
+            Status::Looping if !self.stop_on_loop || self.pending_in_cycle() => None,
+            status => Some(status),
+        }
+    }
+
+    // Whether an nth-execution breakpoint can still fire inside the loop
+    fn pending_in_cycle(&self) -> bool {
+        let cycle = match &self.cycle {
+            Some(cycle) => cycle,
+            None => return false,
+        };
+        self.breakpoints().iter().any(|(_id, b)| match *b {
+            Breakpoint::Nth { pc, n } => self.program.run_count(pc) < n && cycle.contains(&pc),
+            _ => false,
+        })
+    }
+
+    // A looping program repeats from its current pc, so running a copy until
+    // it gets back there visits the whole loop, which is no longer than the code
+    fn find_cycle(&self) -> Vec<usize> {
+        let mut program = self.program.clone();
+        let start = program.pc();
+        let mut cycle = vec![start];
+        for _i in 0..program.code().len() {
+            if !program.step() || program.pc() == start {
+                break;
+            }
+            cycle.push(program.pc());
+        }
+        cycle
+    }
+
+    fn execute(&mut self) {
+        if self.history.len() == self.history_len {
+            self.history.pop_front();
+        }
+        self.history.push_back(self.program.pc());
+        self.last_break = None;
+        self.program.step();
+        if self.cycle.is_none() && self.program.looping() {
+            self.cycle = Some(self.find_cycle());
+        }
+    }
+
+    fn breakpoint_hit(&self) -> Option<usize> {
+        let pc = self.program.pc();
+        self.breakpoints().into_iter().find(|(_id, b)| match *b {
+            Breakpoint::Pc(at) => at == pc,
+            Breakpoint::Nth { pc: at, n } => at == pc && self.program.run_count(pc) + 1 == n,
+            _ => false,
+        }).map(|(id, _b)| id)
+    }
+
+    fn watchpoint_hit(&self, acc_before: isize) -> Option<usize> {
+        let acc = self.program.acc();
+        self.breakpoints().into_iter().find(|(_id, b)| match *b {
+            Breakpoint::Acc(c, v) => c.test(acc, v) && !c.test(acc_before, v),
+            Breakpoint::AccChanged => acc != acc_before,
+            _ => false,
+        }).map(|(id, _b)| id)
+    }
+
+    // Execute up to `count` instructions, stopping early at watchpoints
+    pub fn step(&mut self, count: usize) -> Event {
+        for _i in 0..count {
+            if let Some(status) = self.stopped() {
+                return Event::Stopped(status);
+            }
+            let acc = self.program.acc();
+            self.execute();
+            if let Some(id) = self.watchpoint_hit(acc) {
+                return Event::Break(id);
+            }
+        }
+        match self.stopped() {
+            Some(status) => Event::Stopped(status),
+            None => Event::Stepped,
+        }
+    }
+
+    // Run until a breakpoint or watchpoint is hit or the program stops.
+    // Continuing from a breakpoint runs the instruction it stopped before.
+    pub fn resume(&mut self) -> Event {
+        loop {
+            if let Some(status) = self.stopped() {
+                return Event::Stopped(status);
+            }
+            if self.last_break != Some(self.program.pc()) {
+                if let Some(id) = self.breakpoint_hit() {
+                    self.last_break = Some(self.program.pc());
+                    return Event::Break(id);
+                }
+            }
+            if self.breakpoints.iter().flatten().any(|b| b.is_watchpoint()) {
+                let acc = self.program.acc();
+                self.execute();
+                if let Some(id) = self.watchpoint_hit(acc) {
+                    return Event::Break(id);
+                }
+            } else {
+                self.execute();
+            }
+        }
+    }
+
+    fn describe(&self, event: &Event) -> String {
+        let at = match self.program.code().get(self.program.pc()) {
+            Some(i) => format!("{}: {}", self.program.pc(), i),
+            None => format!("{}: end of program", self.program.pc()),
+        };
+        match event {
+            Event::Stepped => at,
+            Event::Break(id) => format!("breakpoint {} ({}) at {}", id, self.breakpoints[id - 1].unwrap(), at),
+            Event::Stopped(status) => format!("program {} at {}, acc {}", status, self.program.pc(), self.program.acc()),
+        }
+    }
+
+    // Run one debugger command, returning what to print
+    pub fn command(&mut self, line: &str) -> Result<String, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let number = |i: usize| -> Result<usize, String> {
+            words.get(i).ok_or("Missing number")?.parse::<usize>().map_err(|e| e.to_string())
+        };
+        match words.as_slice() {
+            ["step"] | ["s"] | ["step", _] | ["s", _] => {
+                let count = if words.len() == 2 { number(1)? } else { 1 };
+                let event = self.step(count);
+                Ok(self.describe(&event))
+            },
+            ["continue"] | ["c"] => {
+                let event = self.resume();
+                Ok(self.describe(&event))
+            },
+            ["break", _] | ["b", _] => Ok(format!("breakpoint {}", self.add_breakpoint(Breakpoint::Pc(number(1)?)))),
+            ["break", _, _] | ["b", _, _] => {
+                let b = Breakpoint::Nth { pc: number(1)?, n: number(2)? };
+                Ok(format!("breakpoint {}", self.add_breakpoint(b)))
+            },
+            ["watch", "acc"] => Ok(format!("watchpoint {}", self.add_breakpoint(Breakpoint::AccChanged))),
+            ["watch", "acc", c, v] => {
+                let b = Breakpoint::Acc(c.parse()?, v.parse::<isize>().map_err(|e| e.to_string())?);
+                Ok(format!("watchpoint {}", self.add_breakpoint(b)))
+            },
+            ["delete", _] | ["d", _] => match self.remove_breakpoint(number(1)?) {
+                true => Ok(String::from("deleted")),
+                false => Err(format!("No breakpoint {}", words[1])),
+            },
+            ["info"] | ["info", "breakpoints"] => Ok(self.breakpoints().iter()
+                                                      .map(|(id, b)| format!("{}: {}", id, b))
+                                                      .collect::<Vec<String>>()
+                                                      .join("\n")),
+            ["print", "acc"] | ["p", "acc"] => Ok(self.program.acc().to_string()),
+            ["print", "pc"] | ["p", "pc"] => Ok(self.program.pc().to_string()),
+            ["print", "output"] | ["p", "output"] => Ok(format!("{:?}", self.program.output())),
+            ["print", "status"] | ["p", "status"] => Ok(self.program.status().to_string()),
+            ["print", r] | ["p", r] => match r.parse::<Register>() {
+                Ok(r) => Ok(self.program.register(r).to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+            ["backtrace"] | ["bt"] => Ok(self.backtrace().iter()
+                                             .rev()
+                                             .map(|&pc| format!("{}: {}", pc, self.program.code()[pc]))
+                                             .collect::<Vec<String>>()
+                                             .join("\n")),
+            ["list"] | ["l"] => {
+                let pc = self.program.pc();
+                Ok((pc.saturating_sub(3)..(pc + 4).min(self.program.code().len()))
+                    .map(|i| format!("{} {:>4}: {}", if i == pc { "=>" } else { "  " }, i, self.program.code()[i]))
+                    .collect::<Vec<String>>()
+                    .join("\n"))
+            },
+            ["loop", "on"] => { self.stop_on_loop = true; Ok(String::from("stopping on loops")) },
+            ["loop", "off"] => { self.stop_on_loop = false; Ok(String::from("not stopping on loops")) },
+            ["help"] | ["h"] => Ok(String::from(HELP)),
+            _ => Err(format!("Unknown command: {} (try `help`)", line.trim())),
+        }
+    }
+}
+
+const HELP: &str = "\
+step [n]             run one (or n) instructions
+continue             run to the next breakpoint or until the program stops
+break <pc> [n]       break before the instruction at pc (on its nth execution,
+                     running on past the loop stop until it fires)
+watch acc [op v]     break when acc changes (or starts to satisfy e.g. `> 10`)
+delete <id>          remove a breakpoint or watchpoint
+info breakpoints     list breakpoints and watchpoints
+print acc|r1..r7|pc|output|status
+backtrace            recently executed instructions, latest first
+list                 instructions around pc
+loop on|off          whether running an instruction twice stops the program
+quit";
+
+// Read commands from input until `quit` or end of input
+pub fn repl<R: BufRead, W: Write>(debugger: &mut Debugger, input: R, mut output: W) -> io::Result<()> {
+    write!(output, "(dbg) ")?;
+    output.flush()?;
+    for line in input.lines() {
+        let line = line?;
+        match line.trim() {
+            "quit" | "q" => break,
+            "" => (),
+            command => match debugger.command(command) {
+                Ok(s) if s.is_empty() => (),
+                Ok(s) => writeln!(output, "{}", s)?,
+                Err(e) => writeln!(output, "{}", e)?,
+            },
+        }
+        write!(output, "(dbg) ")?;
+        output.flush()?;
+    }
+    Ok(())
+}
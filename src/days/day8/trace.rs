@@ -0,0 +1,154 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use regex::Regex;
+use crate::days::day8::{Instruction, Program, Register};
+use crate::error::{self, AocError};
+
+// One executed instruction and its effect on its register, which is the only
+// one an instruction can write, so a divergence in any register shows up
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub pc: usize,
+    pub instruction: Instruction,
+    pub before: isize,
+    pub after: isize,
+}
+
+impl TraceEntry {
+
+    pub fn parse(line: &str) -> Result<TraceEntry, AocError> {
+        // ex "   7  jmp -4    acc 2 -> 2" or "   3  mul r1 +2  r1 4 -> 8"
+        lazy_static! {
+            static ref ENTRY: Regex = Regex::new(r"^\s*(\d+)\s+([a-z]+(?: [a-z][a-z0-9]*)? [\-\+]\d+)\s+([a-z][a-z0-9]*) (-?\d+) -> (-?\d+)$").unwrap();
+        }
+        let caps = ENTRY.captures(line)
+                        .ok_or_else(|| AocError::parse("expected `pc instruction register before -> after`"))?;
+        let instruction = Instruction::parse(&caps[2])?;
+        if caps[3].parse::<Register>()? != instruction.register() {
+            return Err(AocError::parse(format!("expected the register of `{}`, found `{}`", instruction, &caps[3])));
+        }
+        Ok(TraceEntry {
+            pc: caps[1].parse()?,
+            instruction,
+            before: caps[4].parse()?,
+            after: caps[5].parse()?,
+        })
+    }
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:>4}  {:<8}  {} {} -> {}", self.pc, self.instruction.to_string(), self.instruction.register(), self.before, self.after)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace {
+    entries: Vec<TraceEntry>,
+}
+
+impl Trace {
+
+    pub fn new() -> Trace {
+        Trace { entries: vec![] }
+    }
+
+    pub fn parse(contents: &str) -> Result<Trace, AocError> {
+        Ok(Trace { entries: error::parse_lines(contents, TraceEntry::parse)? })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Trace, AocError> {
+        Trace::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), AocError> {
+        Ok(fs::write(path, self.to_string())?)
+    }
+
+    pub fn push(&mut self, entry: TraceEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // The first step where the two traces differ, or None if they are identical
+    pub fn diff<'a>(&'a self, other: &'a Trace) -> Option<Divergence<'a>> {
+        let step = (0..self.len().max(other.len()))
+            .find(|&i| self.entries.get(i) != other.entries.get(i))?;
+        Some(Divergence { step, original: self, patched: other })
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.entries.iter().try_for_each(|e| writeln!(f, "{}", e))
+    }
+}
+
+// Where a patched program's trace departs from the original's
+#[derive(Debug, Clone, Copy)]
+pub struct Divergence<'a> {
+    pub step: usize,
+    original: &'a Trace,
+    patched: &'a Trace,
+}
+
+impl<'a> Divergence<'a> {
+
+    pub fn original(&self) -> Option<&'a TraceEntry> {
+        self.original.entries.get(self.step)
+    }
+
+    pub fn patched(&self) -> Option<&'a TraceEntry> {
+        self.patched.entries.get(self.step)
+    }
+}
+
+// Shows the last few shared steps, then the rest of both traces as a diff
+// (`-` original, `+` patched) up to a limited number of lines each
+impl<'a> fmt::Display for Divergence<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const CONTEXT: usize = 3;
+        const SHOWN: usize = 10;
+        writeln!(f, "traces diverge at step {}", self.step)?;
+        for e in &self.original.entries[self.step.saturating_sub(CONTEXT)..self.step] {
+            writeln!(f, "  {}", e)?;
+        }
+        for (sign, trace) in [('-', self.original), ('+', self.patched)].iter() {
+            let rest = &trace.entries[self.step..];
+            for e in rest.iter().take(SHOWN) {
+                writeln!(f, "{} {}", sign, e)?;
+            }
+            match rest.len() {
+                0 => writeln!(f, "{} (end of trace)", sign)?,
+                n if n > SHOWN => writeln!(f, "{} ... {} more", sign, n - SHOWN)?,
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+// Run a copy of the program with the instruction at index flipped, returning
+// the traces of the original and the patched run
+pub fn trace_flip(program: &Program, index: usize) -> (Trace, Trace) {
+    let run = |program: &mut Program| {
+        program.record();
+        program.run();
+        program.take_trace().unwrap_or_default()
+    };
+    let mut patched = program.clone();
+    patched.corrupt_instruction(index);
+    (run(&mut program.clone()), run(&mut patched))
+}
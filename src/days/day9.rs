@@ -1,65 +1,122 @@
-use std::fs;
-use itertools::Itertools;
-
-pub fn valid_sums(numbers: &Vec<usize>) -> Vec<usize> {
-    numbers.iter()
-           .combinations(2)
-           .filter(|c| !c.iter().all_equal())
-           .map(|c| c.into_iter().sum())
-           .collect()
-      
+use std::collections::{HashMap, VecDeque};
+use crate::days::{Answer, Solution};
+use crate::error::{self, AocError};
+
+// The last `size` numbers of the stream, with a count of each value so a
+// number can be checked in O(size) and the window slides in O(1)
+#[derive(Debug, Clone)]
+pub struct Window {
+    size: usize,
+    numbers: VecDeque<usize>,
+    counts: HashMap<usize, usize>,
 }
 
-pub fn day9(args: &[String]) -> i32 {
-    println!("Day 9");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return -1;
+impl Window {
+
+    pub fn new(size: usize) -> Window {
+        Window { size, numbers: VecDeque::with_capacity(size + 1), counts: HashMap::new() }
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
 
-    let numbers: Vec<usize> = contents.lines().map(|l| l.parse::<usize>().unwrap()).collect();
+    pub fn is_full(&self) -> bool {
+        self.numbers.len() == self.size
+    }
 
-    let p1 = part1(&numbers, 25).unwrap();
-    println!("Part 1: {}", p1);
+    // A number is valid if it is the sum of two different numbers in the window
+    pub fn is_valid(&self, number: usize) -> bool {
+        self.numbers.iter()
+                    .filter(|&&a| a < number && a * 2 != number)
+                    .any(|a| self.counts.contains_key(&(number - a)))
+    }
 
-    let p2 = part2(&numbers, p1).unwrap();
-    println!("Part 2: {}", p2.0 + p2.1);
-    0
-}
+    // How many different values the window holds
+    pub fn distinct(&self) -> usize {
+        self.counts.len()
+    }
 
-pub fn part1(numbers: &Vec<usize>, count: usize) -> Option<usize> {
-    for i in count..numbers.len() {
-        let sums = valid_sums(&numbers[i-count..i].to_vec());
-        if numbers[i] == 127 {
-            println!("{:?}", &numbers[i-count..i].to_vec());
-        }
-        match sums.iter().find(|&&s| s == numbers[i]) {
-            None => return Some(numbers[i]),
-            _ => (),
+    pub fn push(&mut self, number: usize) {
+        self.numbers.push_back(number);
+        *self.counts.entry(number).or_insert(0) += 1;
+        if self.numbers.len() > self.size {
+            if let Some(old) = self.numbers.pop_front() {
+                // drop values that left the window so the map stays bounded
+                if let Some(c) = self.counts.get_mut(&old) {
+                    *c -= 1;
+                    if *c == 0 {
+                        self.counts.remove(&old);
+                    }
+                }
+            }
         }
     }
-    return None;
 }
 
-pub fn part2(numbers: &Vec<usize>, target: usize) -> Option<(usize, usize)> {
-    for i in 0..numbers.len() {
-        let mut sum = 0;
-        for j in i..numbers.len() {
-            if sum > target {
-                break;
-            }
-            if sum < target {
-                sum += numbers[j];
-            } else {
-                // matching sum
-                let n = &numbers[i..j].to_vec();
-                return Some((*n.iter().min().unwrap(), *n.iter().max().unwrap()));
+// Streams the numbers that aren't valid against the preceding window
+#[derive(Debug, Clone)]
+pub struct Validator<I> {
+    numbers: I,
+    window: Window,
+}
+
+impl<I: Iterator<Item = usize>> Iterator for Validator<I> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        for number in &mut self.numbers {
+            let valid = !self.window.is_full() || self.window.is_valid(number);
+            self.window.push(number);
+            if !valid {
+                return Some(number);
             }
         }
+        None
+    }
+}
+
+pub fn invalid_numbers<I: IntoIterator<Item = usize>>(numbers: I, preamble: usize) -> Validator<I::IntoIter> {
+    Validator { numbers: numbers.into_iter(), window: Window::new(preamble) }
+}
+
+#[derive(Debug, Clone)]
+pub struct Day9 {
+    numbers: Vec<usize>,
+}
+
+impl Solution for Day9 {
+
+    fn parse(contents: &str) -> Result<Day9, AocError> {
+        Ok(Day9 { numbers: error::parse_lines(contents, error::parse_number)? })
+    }
+
+    fn part1(&self) -> Answer {
+        part1(&self.numbers, 25).into()
+    }
+
+    fn part2(&self) -> Answer {
+        part1(&self.numbers, 25)
+            .and_then(|p1| part2(&self.numbers, p1))
+            .map(|(min, max)| min + max)
+            .into()
+    }
+}
+
+pub fn part1(numbers: &[usize], count: usize) -> Option<usize> {
+    invalid_numbers(numbers.iter().copied(), count).next()
+}
+
+// Find a contiguous range of at least two numbers adding up to target by
+// growing the range on the right and shrinking it from the left
+pub fn part2(numbers: &[usize], target: usize) -> Option<(usize, usize)> {
+    let (mut start, mut sum) = (0, 0);
+    for (end, n) in numbers.iter().enumerate() {
+        sum += n;
+        while sum > target && start < end {
+            sum -= numbers[start];
+            start += 1;
+        }
+        if sum == target && start < end {
+            let range = &numbers[start..=end];
+            return Some((*range.iter().min()?, *range.iter().max()?));
+        }
     }
-    return None;
+    None
 }
@@ -1,3 +1,7 @@
+use std::convert::TryFrom;
+use std::fmt;
+use crate::error::AocError;
+
 pub mod utils;
 pub mod day1;
 pub mod day2;
@@ -17,10 +21,142 @@ pub mod day15;
 pub mod day16;
 pub mod day17;
 pub mod day18;
-//pub mod day19;
+pub mod day19;
 pub mod day20;
-//pub mod day21;
+pub mod day21;
 pub mod day22;
 pub mod day23;
 pub mod day24;
-// pub mod day25;
\ No newline at end of file
+pub mod day25;
+
+/// The answer produced by one part of a day's puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+// Numbers too large for an i64 are kept as text rather than wrapping
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    match i64::try_from(n) {
+                        Ok(n) => Answer::Number(n),
+                        Err(_) => Answer::Text(n.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(o: Option<T>) -> Answer {
+        match o {
+            Some(a) => a.into(),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+/// A day's puzzle: parse the input once, then answer both parts from it.
+pub trait Solution {
+    fn parse(contents: &str) -> Result<Self, AocError> where Self: Sized;
+    fn part1(&self) -> Answer;
+    fn part2(&self) -> Answer;
+}
+
+/// Registry entry for an implemented day.
+pub struct Day {
+    pub number: u8,
+    parse: fn(&str) -> Result<Box<dyn Solution>, AocError>,
+}
+
+impl Day {
+    pub fn name(&self) -> String {
+        format!("day{}", self.number)
+    }
+
+    /// The puzzle input by convention, `data/dayN.txt`.
+    pub fn data_file(&self) -> String {
+        format!("data/{}.txt", self.name())
+    }
+
+    pub fn parse(&self, contents: &str) -> Result<Box<dyn Solution>, AocError> {
+        (self.parse)(contents)
+    }
+}
+
+fn boxed<S: Solution + 'static>(contents: &str) -> Result<Box<dyn Solution>, AocError> {
+    Ok(Box::new(S::parse(contents)?))
+}
+
+static DAYS: &[Day] = &[
+    Day { number: 1, parse: boxed::<day1::Day1> },
+    Day { number: 2, parse: boxed::<day2::Day2> },
+    Day { number: 3, parse: boxed::<day3::Day3> },
+    Day { number: 4, parse: boxed::<day4::Day4> },
+    Day { number: 5, parse: boxed::<day5::Day5> },
+    Day { number: 6, parse: boxed::<day6::Day6> },
+    Day { number: 7, parse: boxed::<day7::Day7> },
+    Day { number: 8, parse: boxed::<day8::Day8> },
+    Day { number: 9, parse: boxed::<day9::Day9> },
+    Day { number: 10, parse: boxed::<day10::Day10> },
+    Day { number: 11, parse: boxed::<day11::Day11> },
+    Day { number: 12, parse: boxed::<day12::Day12> },
+    Day { number: 13, parse: boxed::<day13::Day13> },
+    Day { number: 14, parse: boxed::<day14::Day14> },
+    Day { number: 15, parse: boxed::<day15::Day15> },
+    Day { number: 16, parse: boxed::<day16::Day16> },
+    Day { number: 17, parse: boxed::<day17::Day17> },
+    Day { number: 18, parse: boxed::<day18::Day18> },
+    Day { number: 19, parse: boxed::<day19::Day19> },
+    Day { number: 20, parse: boxed::<day20::Day20> },
+    Day { number: 21, parse: boxed::<day21::Day21> },
+    Day { number: 22, parse: boxed::<day22::Day22> },
+    Day { number: 23, parse: boxed::<day23::Day23> },
+    Day { number: 24, parse: boxed::<day24::Day24> },
+    Day { number: 25, parse: boxed::<day25::Day25> },
+];
+
+/// All implemented days, in order.
+pub fn all() -> &'static [Day] {
+    DAYS
+}
+
+pub fn get(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
+
+/// Look up a day by name, accepting either "day5" or "5".
+pub fn find(name: &str) -> Option<&'static Day> {
+    let number = name.strip_prefix("day").unwrap_or(name);
+    get(number.parse::<u8>().ok()?)
+}
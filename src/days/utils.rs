@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 // From https://rosettacode.org/wiki/Chinese_remainder_theorem#Rust
 
 fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
@@ -18,7 +20,7 @@ fn mod_inv(x: i64, n: i64) -> Option<i64> {
     }
 }
  
-pub fn chinese_remainder(residues: &Vec<i64>, modulii: &Vec<i64>) -> Option<i64> {
+pub fn chinese_remainder(residues: &[i64], modulii: &[i64]) -> Option<i64> {
     let prod = modulii.iter().product::<i64>();
  
     let mut sum = 0;
@@ -30,4 +32,46 @@ pub fn chinese_remainder(residues: &Vec<i64>, modulii: &Vec<i64>) -> Option<i64>
  
     Some(sum % prod)
 }
- 
\ No newline at end of file
+ 
+// (base ^ exp) % modulus by repeated squaring
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+    let (mut result, mut base, mut exp) = (1u128, base as u128 % modulus as u128, exp);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus as u128;
+        }
+        base = base * base % modulus as u128;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+// Smallest x with (base ^ x) % modulus == target, using baby-step giant-step
+// in O(sqrt(modulus)) time and space. base must be invertible mod modulus.
+pub fn discrete_log(base: u64, target: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 || modulus > i64::MAX as u64 {
+        return None;
+    }
+    let target = target % modulus;
+    let m = (modulus as f64).sqrt().ceil() as u64;
+    // baby steps: base ^ j for j in 0..m, keeping the smallest j
+    let mut baby = HashMap::new();
+    let mut e = 1 % modulus;
+    for j in 0..m {
+        baby.entry(e).or_insert(j);
+        e = (e as u128 * base as u128 % modulus as u128) as u64;
+    }
+    // giant steps: target * base ^ (-m * i)
+    let factor = mod_inv(mod_pow(base, m, modulus) as i64, modulus as i64)? as u64;
+    let mut gamma = target;
+    for i in 0..m {
+        if let Some(j) = baby.get(&gamma) {
+            return Some(i * m + j);
+        }
+        gamma = (gamma as u128 * factor as u128 % modulus as u128) as u64;
+    }
+    None
+}
@@ -0,0 +1,137 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+
+/// Errors raised while reading or parsing puzzle input.
+///
+/// Parse errors carry the 1-based line (and, where known, column) of the
+/// offending input so the CLI can point at it.
+#[derive(Debug)]
+pub enum AocError {
+    Io(io::Error),
+    Parse {
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+}
+
+impl AocError {
+
+    pub fn parse<S: Into<String>>(message: S) -> AocError {
+        AocError::Parse { line: None, column: None, message: message.into() }
+    }
+
+    /// Attach a line number, unless a more specific one is already set.
+    pub fn at_line(self, line: usize) -> AocError {
+        match self {
+            AocError::Parse { line: None, column, message } => AocError::Parse { line: Some(line), column, message },
+            e => e,
+        }
+    }
+
+    /// Attach a column number, unless a more specific one is already set.
+    pub fn at_column(self, column: usize) -> AocError {
+        match self {
+            AocError::Parse { line, column: None, message } => AocError::Parse { line, column: Some(column), message },
+            e => e,
+        }
+    }
+
+    /// Shift a line number relative to a block of input to be relative to
+    /// the whole input, where the block starts after `offset` lines.
+    pub fn offset_line(self, offset: usize) -> AocError {
+        match self {
+            AocError::Parse { line: Some(line), column, message } => AocError::Parse { line: Some(line + offset), column, message },
+            e => e.at_line(offset + 1),
+        }
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            AocError::Parse { line, .. } => *line,
+            AocError::Io(_) => None,
+        }
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            AocError::Parse { column, .. } => *column,
+            AocError::Io(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Io(e) => write!(f, "{}", e),
+            AocError::Parse { line: Some(line), column: Some(column), message } =>
+                write!(f, "line {}, column {}: {}", line, column, message),
+            AocError::Parse { line: Some(line), column: None, message } => write!(f, "line {}: {}", line, message),
+            AocError::Parse { line: None, column: Some(column), message } => write!(f, "column {}: {}", column, message),
+            AocError::Parse { line: None, column: None, message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io(e) => Some(e),
+            AocError::Parse { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> AocError {
+        AocError::Io(e)
+    }
+}
+
+impl From<ParseIntError> for AocError {
+    fn from(e: ParseIntError) -> AocError {
+        AocError::parse(format!("invalid number: {}", e))
+    }
+}
+
+/// Parse each line of the input, tagging any error with its line number.
+pub fn parse_lines<T, F>(contents: &str, mut f: F) -> Result<Vec<T>, AocError>
+    where F: FnMut(&str) -> Result<T, AocError>
+{
+    contents.lines()
+            .enumerate()
+            .map(|(i, l)| f(l).map_err(|e| e.at_line(i + 1)))
+            .collect()
+}
+
+/// Parse each blank-line separated group of the input. Line numbers reported
+/// by `f` are relative to its group and are shifted to match the whole input.
+pub fn parse_groups<T, F>(contents: &str, mut f: F) -> Result<Vec<T>, AocError>
+    where F: FnMut(&str) -> Result<T, AocError>
+{
+    let mut offset = 0;
+    let mut groups = Vec::new();
+    for group in contents.split("\n\n") {
+        groups.push(f(group).map_err(|e| e.offset_line(offset))?);
+        offset += group.lines().count() + 1;
+    }
+    Ok(groups)
+}
+
+/// Check that a row of a grid is as wide as the first, whose width is kept
+/// in `width`. The error points at the column where the rows disagree.
+pub fn same_width(width: &mut Option<usize>, row: usize) -> Result<(), AocError> {
+    match *width.get_or_insert(row) {
+        w if w == row => Ok(()),
+        w => Err(AocError::parse(format!("expected a row of {} cells like the first, found {}", w, row))
+                     .at_column(w.min(row) + 1)),
+    }
+}
+
+/// Parse a single number, e.g. one line of a list of numbers.
+pub fn parse_number<T: std::str::FromStr<Err = ParseIntError>>(s: &str) -> Result<T, AocError> {
+    s.trim().parse::<T>().map_err(|_| AocError::parse(format!("expected a number, found `{}`", s)))
+}
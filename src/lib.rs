@@ -1,4 +1,9 @@
 #[macro_use]
 extern crate lazy_static;
 
-pub mod days;
\ No newline at end of file
+pub mod answers;
+pub mod days;
+pub mod error;
+pub mod memory;
+pub mod output;
+pub mod runner;
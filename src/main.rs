@@ -1,46 +1,294 @@
 #![recursion_limit = "64"]
 
 use std::env;
+use std::fs;
+use std::io;
+use aoc20::answers::{self, Answers};
 use aoc20::days;
+use aoc20::days::day8::{InstructionSet, LoopDetection, Opcode, Program};
+use aoc20::days::day8::asm;
+use aoc20::days::day8::debugger::{self, Debugger};
+use aoc20::days::day8::trace::Trace;
+use aoc20::output::{self, Format};
+use aoc20::runner::{self, Status};
 
-fn main() {
-    println!("Advent of Code 2020");
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        println!("Missing input day");
-        std::process::exit(1);
+#[derive(Debug, Clone)]
+struct Options {
+    format: Format,
+    input: Option<String>,
+    jobs: usize,
+    args: Vec<String>,
+}
+
+impl Options {
+
+    // pull the `--format`, `--input` and `--jobs` options out, leaving the positional arguments
+    fn parse(args: &[String]) -> Result<Options, String> {
+        let mut format = Format::Text;
+        let mut input = None;
+        let mut jobs = 1;
+        let mut positional = Vec::new();
+        let mut iter = args.iter();
+        while let Some(a) = iter.next() {
+            if a == "--format" {
+                format = iter.next().ok_or("Missing value for --format")?.parse()?;
+            } else if let Some(f) = a.strip_prefix("--format=") {
+                format = f.parse()?;
+            } else if a == "--input" {
+                input = Some(iter.next().ok_or("Missing value for --input")?.clone());
+            } else if let Some(i) = a.strip_prefix("--input=") {
+                input = Some(i.to_string());
+            } else if a == "--jobs" || a == "-j" {
+                jobs = Options::jobs(iter.next().ok_or("Missing value for --jobs")?)?;
+            } else if let Some(j) = a.strip_prefix("--jobs=") {
+                jobs = Options::jobs(j)?;
+            } else {
+                positional.push(a.clone());
+            }
+        }
+        Ok(Options { format, input, jobs, args: positional })
+    }
+
+    fn jobs(value: &str) -> Result<usize, String> {
+        match value.parse::<usize>() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(format!("Invalid value for --jobs: {} (expected a positive number)", value)),
+        }
     }
-    let (day, dayargs) = (&args[1], &args[2..]);
-    match day.as_ref() {
-        "day1" => days::day1::day1(dayargs),
-        "day2" => days::day2::day2(dayargs),
-        "day3" => days::day3::day3(dayargs),
-        "day4" => days::day4::day4(dayargs),
-        "day5" => days::day5::day5(dayargs),
-        "day6" => days::day6::day6(dayargs),
-        "day7" => days::day7::day7(dayargs),
-        "day8" => days::day8::day8(dayargs),
-        "day9" => days::day9::day9(dayargs),
-        "day10" => days::day10::day10(dayargs),
-        "day11" => days::day11::day11(dayargs),
-        "day12" => days::day12::day12(dayargs),
-        "day13" => days::day13::day13(dayargs),
-        "day14" => days::day14::day14(dayargs),
-        "day15" => days::day15::day15(dayargs),
-        "day16" => days::day16::day16(dayargs),
-        "day17" => days::day17::day17(dayargs),
-        "day18" => days::day18::day18(dayargs),
-        //"day19" => days::day19::day19(dayargs),
-        "day20" => days::day20::day20(dayargs),
-        //"day21" => days::day21::day21(dayargs),
-        "day22" => days::day22::day22(dayargs),
-        "day23" => days::day23::day23(dayargs),
-        "day24" => days::day24::day24(dayargs),
-        // "day25" => days::day25::day25(dayargs),
+}
+
+fn run_all(options: &Options) -> i32 {
+    output::print_header(options.format);
+    let reports = run_days(options, |r| output::print_report(options.format, r));
+    if reports.iter().any(|r| r.is_failure()) {
+        return 1;
+    }
+    0
+}
+
+// run every day, on a thread pool when more than one job is requested
+fn run_days<F: FnMut(&runner::DayReport)>(options: &Options, report: F) -> Vec<runner::DayReport> {
+    if options.jobs > 1 {
+        runner::run_all_parallel(options.jobs, report)
+    } else {
+        runner::run_all(report)
+    }
+}
+
+fn verify(args: &[String], options: &Options) -> i32 {
+    let filename = args.first().map(|f| f.as_str()).unwrap_or("data/answers.toml");
+    let answers = match Answers::load(filename) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    let mut failed = false;
+    run_days(options, |r| {
+        for (part, verdict) in answers::verify(r, &answers) {
+            failed |= verdict.is_failure();
+            println!("{:<6} part{:<2} {}", format!("day{}", r.day), part, verdict);
+        }
+    });
+    if failed {
+        return 1;
+    }
+    0
+}
+
+// day 8 boot code, raw or with labels and comments
+fn load_program(filename: &str) -> Result<Program, String> {
+    let contents = fs::read_to_string(filename).map_err(|e| format!("{}: {}", filename, e))?;
+    let code = asm::assemble(&contents).map_err(|e| format!("{}: {}", filename, e))?;
+    let jumps_on_registers = code.iter().any(|i| i.opcode() == Opcode::Jz);
+    let mut program = Program::with_instruction_set(code, InstructionSet::extended());
+    // without conditional jumps, running an instruction twice already means a
+    // loop, and registers changing on every pass would hide it
+    if !jumps_on_registers {
+        program.set_loop_detection(LoopDetection::Pc);
+    }
+    Ok(program)
+}
+
+// lower boot code assembly to the raw `acc +6` form
+fn assemble(args: &[String]) -> i32 {
+    let filename = args.first().map(|f| f.as_str()).unwrap_or("data/day8.txt");
+    match load_program(filename) {
+        Ok(program) => program.code().iter().for_each(|i| println!("{}", i)),
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    }
+    0
+}
+
+// render boot code with generated labels for its jump targets
+fn disassemble(args: &[String]) -> i32 {
+    let filename = args.first().map(|f| f.as_str()).unwrap_or("data/day8.txt");
+    match load_program(filename) {
+        Ok(program) => print!("{}", asm::disassemble(program.code())),
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    }
+    0
+}
+
+// step through a day 8 program, reading debugger commands from stdin
+fn debug(args: &[String]) -> i32 {
+    let filename = args.first().map(|f| f.as_str()).unwrap_or("data/day8.txt");
+    let mut debugger = match load_program(filename) {
+        Ok(program) => Debugger::new(program),
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    if let Err(e) = debugger::repl(&mut debugger, io::stdin().lock(), io::stdout()) {
+        eprintln!("{}", e);
+        return 1;
+    }
+    0
+}
+
+// record the execution trace of a day 8 program, optionally with one instruction
+// flipped, printing it or saving it to a file
+fn trace(args: &[String]) -> i32 {
+    let filename = args.first().map(|f| f.as_str()).unwrap_or("data/day8.txt");
+    let mut program = match load_program(filename) {
+        Ok(program) => program,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    // `-` leaves the program unpatched
+    match args.get(1).filter(|i| *i != "-").map(|i| i.parse::<usize>()) {
+        Some(Ok(i)) if i < program.code().len() => program.corrupt_instruction(i),
+        Some(_) => {
+            eprintln!("Invalid instruction index: {}", args[1]);
+            return 1;
+        }
+        None => (),
+    }
+    program.record();
+    program.run();
+    let trace = program.take_trace().unwrap_or_default();
+    match args.get(2) {
+        Some(out) => if let Err(e) = trace.save(out) {
+            eprintln!("{}: {}", out, e);
+            return 1;
+        },
+        None => print!("{}", trace),
+    }
+    // on stderr so a printed trace can be redirected and replayed
+    eprintln!("Program {} after {} steps, acc {}", program.status(), trace.len(), program.acc());
+    0
+}
+
+// compare two saved traces and show where they diverge
+fn replay(args: &[String]) -> i32 {
+    if args.len() != 2 {
+        eprintln!("Expected an original and a patched trace file");
+        return 1;
+    }
+    let traces = match (Trace::load(&args[0]), Trace::load(&args[1])) {
+        (Ok(original), Ok(patched)) => (original, patched),
+        (Err(e), _) => {
+            eprintln!("{}: {}", args[0], e);
+            return 1;
+        }
+        (_, Err(e)) => {
+            eprintln!("{}: {}", args[1], e);
+            return 1;
+        }
+    };
+    match traces.0.diff(&traces.1) {
+        Some(d) => print!("{}", d),
+        None => println!("traces are identical"),
+    }
+    0
+}
+
+fn run_day(name: &str, dayargs: &[String], options: &Options) -> i32 {
+    let day = match days::find(name) {
+        Some(day) => day,
+        None => {
+            eprintln!("Unknown day: {}", name);
+            return 1;
+        }
+    };
+    // input comes from `--input`, stdin (`-`), a file, or data/dayN.txt by default
+    let report = match (&options.input, dayargs) {
+        (Some(contents), []) => runner::run_str(day, "<inline>", contents),
+        (None, [file]) if file == "-" => runner::run_stdin(day),
+        (None, [file]) => runner::run_file(day, file),
+        (None, []) => runner::run_day(day),
         _ => {
-            println!("Unknown day: {}", day);
-            0
+            eprintln!("Expected a single input: a file, `-` for stdin, or --input");
+            return 1;
         }
     };
+    if options.format == Format::Text {
+        println!("Day {}", day.number);
+        println!("In file {}", report.input);
+        if let Some(u) = &report.parse {
+            println!("Parsed in {}", u);
+        }
+        match &report.status {
+            Status::Solved(parts) => parts.iter().for_each(|p| match &p.answer {
+                Ok(a) => println!("Part {}: {} ({})", p.part, a, p.usage),
+                Err(e) => println!("Part {}: panicked: {} ({})", p.part, e, p.usage),
+            }),
+            Status::MissingInput => println!("Input file not found"),
+            Status::Failed(e) => println!("{} {}", day.name(), e),
+        }
+    } else {
+        output::print_header(options.format);
+        output::print_report(options.format, &report);
+    }
+    if report.is_failure() || matches!(report.status, Status::MissingInput) {
+        return 1;
+    }
+    0
 }
 
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match Options::parse(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    if options.args.is_empty() {
+        eprintln!("Missing input day");
+        std::process::exit(1);
+    }
+    let args = &options.args[1..];
+    // the day 8 tools write programs and traces to stdout, so no banner for them
+    let tool: Option<fn(&[String]) -> i32> = match options.args[0].as_str() {
+        "debug" => Some(debug),
+        "asm" => Some(assemble),
+        "disasm" => Some(disassemble),
+        "trace" => Some(trace),
+        "replay" => Some(replay),
+        _ => None,
+    };
+    if let Some(tool) = tool {
+        std::process::exit(tool(args));
+    }
+    if options.format == Format::Text {
+        println!("Advent of Code 2020");
+    }
+    let code = match options.args[0].as_str() {
+        "run-all" => run_all(&options),
+        "verify" => verify(args, &options),
+        day => run_day(day, args, &options),
+    };
+    std::process::exit(code);
+}
@@ -0,0 +1,114 @@
+//! Heap usage tracking.
+//!
+//! With the `alloc-stats` feature the crate installs a global allocator that
+//! counts live heap bytes and the high-water mark. Without it the functions
+//! here are no-ops and no peak is reported.
+//!
+//! The counters are process wide, so a peak measured while other threads
+//! allocate includes their usage too. The runner therefore leaves peaks out
+//! when running days in parallel.
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    pub static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    pub static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    pub struct Counting;
+
+    fn grow(size: usize) {
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn shrink(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let p = System.alloc(layout);
+            if !p.is_null() {
+                grow(layout.size());
+            }
+            p
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let p = System.alloc_zeroed(layout);
+            if !p.is_null() {
+                grow(layout.size());
+            }
+            p
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            shrink(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let p = System.realloc(ptr, layout, new_size);
+            if !p.is_null() {
+                if new_size > layout.size() {
+                    grow(new_size - layout.size());
+                } else {
+                    shrink(layout.size() - new_size);
+                }
+            }
+            p
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+}
+
+/// Whether heap usage is being counted.
+pub fn enabled() -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+/// Bytes currently allocated on the heap, if counted.
+pub fn current() -> Option<usize> {
+    #[cfg(feature = "alloc-stats")]
+    {
+        use std::sync::atomic::Ordering;
+        Some(counting::CURRENT.load(Ordering::Relaxed))
+    }
+    #[cfg(not(feature = "alloc-stats"))]
+    {
+        None
+    }
+}
+
+/// Start a new measurement: reset the high-water mark to the current usage
+/// and return that usage as the baseline for `peak_since`.
+pub fn reset_peak() -> Option<usize> {
+    #[cfg(feature = "alloc-stats")]
+    {
+        use std::sync::atomic::Ordering;
+        let current = counting::CURRENT.load(Ordering::Relaxed);
+        counting::PEAK.store(current, Ordering::Relaxed);
+        Some(current)
+    }
+    #[cfg(not(feature = "alloc-stats"))]
+    {
+        None
+    }
+}
+
+/// Peak bytes allocated above `baseline` since the matching `reset_peak`.
+pub fn peak_since(baseline: Option<usize>) -> Option<usize> {
+    #[cfg(feature = "alloc-stats")]
+    {
+        use std::sync::atomic::Ordering;
+        baseline.map(|b| counting::PEAK.load(Ordering::Relaxed).saturating_sub(b))
+    }
+    #[cfg(not(feature = "alloc-stats"))]
+    {
+        baseline.map(|_| 0)
+    }
+}
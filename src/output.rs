@@ -0,0 +1,194 @@
+use std::fmt::Write;
+use std::str::FromStr;
+use crate::days::Answer;
+use crate::runner::{self, DayReport, PartResult, Status, Usage};
+
+/// How the binary renders day reports.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Unknown format: {} (expected text, json or csv)", s)),
+        }
+    }
+}
+
+/// One output record: a single part of a day, or the whole day when it did not
+/// run. A day that ran also gets a `parsed` record with the cost of parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub day: u8,
+    pub part: Option<u8>,
+    pub status: &'static str,
+    pub answer: Option<Answer>,
+    pub error: Option<String>,
+    pub elapsed_ns: Option<u128>,
+    pub peak_bytes: Option<usize>,
+    pub input: String,
+}
+
+impl Row {
+
+    fn part(r: &DayReport, p: &PartResult) -> Row {
+        let (status, answer, error) = match &p.answer {
+            Ok(Answer::Unsolved) => ("unsolved", None, None),
+            Ok(a) => ("ok", Some(a.clone()), None),
+            Err(e) => ("panicked", None, Some(e.clone())),
+        };
+        Row {
+            day: r.day,
+            part: Some(p.part),
+            status,
+            answer,
+            error,
+            elapsed_ns: Some(p.usage.elapsed.as_nanos()),
+            peak_bytes: p.usage.peak_bytes,
+            input: r.input.clone(),
+        }
+    }
+
+    fn parse(r: &DayReport, usage: &Usage) -> Row {
+        Row {
+            elapsed_ns: Some(usage.elapsed.as_nanos()),
+            peak_bytes: usage.peak_bytes,
+            ..Row::day(r, "parsed", None)
+        }
+    }
+
+    fn day(r: &DayReport, status: &'static str, error: Option<String>) -> Row {
+        Row { day: r.day, part: None, status, answer: None, error, elapsed_ns: None, peak_bytes: None, input: r.input.clone() }
+    }
+}
+
+/// Flatten a report into its output rows.
+pub fn rows(r: &DayReport) -> Vec<Row> {
+    match &r.status {
+        Status::Solved(parts) => r.parse.iter().map(|u| Row::parse(r, u))
+                                  .chain(parts.iter().map(|p| Row::part(r, p)))
+                                  .collect(),
+        Status::MissingInput => vec![Row::day(r, "missing", None)],
+        Status::Failed(e) => vec![Row::day(r, "failed", Some(e.clone()))],
+    }
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => { write!(out, "\\u{:04x}", c as u32).unwrap(); },
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn json_option<T, F: Fn(&T) -> String>(o: &Option<T>, f: F) -> String {
+    match o {
+        Some(v) => f(v),
+        None => String::from("null"),
+    }
+}
+
+fn json_answer(a: &Answer) -> String {
+    match a {
+        Answer::Number(n) => n.to_string(),
+        Answer::Text(s) => json_string(s),
+        Answer::Unsolved => String::from("null"),
+    }
+}
+
+pub fn to_json(row: &Row) -> String {
+    format!("{{\"day\":{},\"part\":{},\"status\":{},\"answer\":{},\"error\":{},\"elapsed_ns\":{},\"peak_bytes\":{},\"input\":{}}}",
+        row.day,
+        json_option(&row.part, |p| p.to_string()),
+        json_string(row.status),
+        json_option(&row.answer, json_answer),
+        json_option(&row.error, |e| json_string(e)),
+        json_option(&row.elapsed_ns, |t| t.to_string()),
+        json_option(&row.peak_bytes, |b| b.to_string()),
+        json_string(&row.input))
+}
+
+pub const CSV_HEADER: &str = "day,part,status,answer,error,elapsed_ns,peak_bytes,input";
+
+pub fn to_csv(row: &Row) -> String {
+    let answer = match &row.answer {
+        Some(Answer::Unsolved) | None => String::new(),
+        Some(a) => a.to_string(),
+    };
+    [
+        row.day.to_string(),
+        row.part.map(|p| p.to_string()).unwrap_or_default(),
+        row.status.to_string(),
+        answer,
+        row.error.clone().unwrap_or_default(),
+        row.elapsed_ns.map(|t| t.to_string()).unwrap_or_default(),
+        row.peak_bytes.map(|b| b.to_string()).unwrap_or_default(),
+        row.input.clone(),
+    ].iter().map(|f| csv_field(f)).collect::<Vec<String>>().join(",")
+}
+
+fn peak(usage: &Usage) -> String {
+    usage.peak_bytes.map(runner::format_bytes).unwrap_or_else(|| String::from("-"))
+}
+
+fn print_text(r: &DayReport) {
+    let day = format!("day{}", r.day);
+    match &r.status {
+        Status::Solved(parts) => {
+            if let Some(u) = &r.parse {
+                println!("{:<6} {:<5} {:<20} {:>12} {:>10}", day, "parse", "", format!("{:.2?}", u.elapsed), peak(u));
+            }
+            for p in parts {
+                let answer = match &p.answer {
+                    Ok(a) => a.to_string(),
+                    Err(e) => format!("panicked: {}", e),
+                };
+                println!("{:<6} {:<5} {:<20} {:>12} {:>10}", day, p.part, answer, format!("{:.2?}", p.usage.elapsed), peak(&p.usage));
+            }
+        },
+        Status::MissingInput => println!("{:<6} {:<5} no input ({})", day, "-", r.input),
+        Status::Failed(e) => println!("{:<6} {:<5} failed: {}", day, "-", e),
+    }
+}
+
+pub fn print_header(format: Format) {
+    match format {
+        Format::Text => println!("{:<6} {:<5} {:<20} {:>12} {:>10}", "Day", "Part", "Answer", "Time", "Peak"),
+        Format::Json => (),
+        Format::Csv => println!("{}", CSV_HEADER),
+    }
+}
+
+pub fn print_report(format: Format, r: &DayReport) {
+    match format {
+        Format::Text => print_text(r),
+        Format::Json => rows(r).iter().for_each(|row| println!("{}", to_json(row))),
+        Format::Csv => rows(r).iter().for_each(|row| println!("{}", to_csv(row))),
+    }
+}
@@ -0,0 +1,219 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::days::{self, Answer, Day};
+use crate::memory;
+
+/// Wall time and peak heap use of one step of a day. The peak is only
+/// known when built with the `alloc-stats` feature.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub elapsed: Duration,
+    pub peak_bytes: Option<usize>,
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.2?}", self.elapsed)?;
+        if let Some(peak) = self.peak_bytes {
+            write!(f, ", peak {}", format_bytes(peak))?;
+        }
+        Ok(())
+    }
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Outcome of running one part of a day.
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Result<Answer, String>,
+    pub usage: Usage,
+}
+
+/// Outcome of running a whole day against its input.
+#[derive(Debug, Clone)]
+pub enum Status {
+    Solved(Vec<PartResult>),
+    MissingInput,
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    pub day: u8,
+    pub input: String,
+    // how long parsing took, when it succeeded
+    pub parse: Option<Usage>,
+    pub status: Status,
+}
+
+impl DayReport {
+
+    // a day fails if it could not be read or parsed, or if either part panicked
+    pub fn is_failure(&self) -> bool {
+        match &self.status {
+            Status::Solved(parts) => parts.iter().any(|p| p.answer.is_err()),
+            Status::MissingInput => false,
+            Status::Failed(_) => true,
+        }
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        String::from("panicked")
+    }
+}
+
+fn catch<T, F: FnOnce() -> T>(f: F) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_message)
+}
+
+fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Usage) {
+    let baseline = memory::reset_peak();
+    let start = Instant::now();
+    let r = f();
+    let elapsed = start.elapsed();
+    (r, Usage { elapsed, peak_bytes: memory::peak_since(baseline) })
+}
+
+/// Parse the input and run both parts, catching any panic along the way.
+/// Returns the cost of parsing along with each part's result.
+pub fn run(day: &Day, contents: &str) -> Result<(Usage, Vec<PartResult>), String> {
+    let (solution, parse) = measure(|| catch(|| day.parse(contents)));
+    let solution = solution?.map_err(|e| e.to_string())?;
+    let mut parts = Vec::new();
+    for part in 1..=2 {
+        let (answer, usage) = measure(|| catch(|| match part {
+            1 => solution.part1(),
+            _ => solution.part2(),
+        }));
+        parts.push(PartResult { part, answer, usage });
+    }
+    Ok((parse, parts))
+}
+
+/// Run a day against the input in the given file.
+pub fn run_file(day: &Day, input: &str) -> DayReport {
+    report(day, input, fs::read_to_string(input))
+}
+
+/// Run a day against input already in memory, e.g. passed inline on the
+/// command line. `input` only names the source in the report.
+pub fn run_str(day: &Day, input: &str, contents: &str) -> DayReport {
+    report(day, input, Ok(contents.to_string()))
+}
+
+/// Run a day against everything read from stdin.
+pub fn run_stdin(day: &Day) -> DayReport {
+    let mut contents = String::new();
+    let read = io::stdin().read_to_string(&mut contents).map(|_| contents);
+    report(day, "<stdin>", read)
+}
+
+fn report(day: &Day, input: &str, contents: io::Result<String>) -> DayReport {
+    let (parse, status) = match contents {
+        Ok(contents) => match run(day, &contents) {
+            Ok((parse, parts)) => (Some(parse), Status::Solved(parts)),
+            Err(e) => (None, Status::Failed(e)),
+        },
+        Err(e) if e.kind() == io::ErrorKind::NotFound => (None, Status::MissingInput),
+        Err(e) => (None, Status::Failed(e.to_string())),
+    };
+    DayReport { day: day.number, input: input.to_string(), parse, status }
+}
+
+/// Run a day against its conventional `data/dayN.txt` input.
+pub fn run_day(day: &Day) -> DayReport {
+    run_file(day, &day.data_file())
+}
+
+/// Run every registered day in order, handing each report to `report` as it completes.
+pub fn run_all<F: FnMut(&DayReport)>(mut report: F) -> Vec<DayReport> {
+    days::all().iter()
+               .map(run_day)
+               .inspect(|r| report(r))
+               .collect()
+}
+
+/// Run every registered day on `workers` threads, see `run_parallel`.
+pub fn run_all_parallel<F: FnMut(&DayReport)>(workers: usize, report: F) -> Vec<DayReport> {
+    run_parallel(days::all(), workers, report)
+}
+
+// Drop the heap peaks from a report. The allocation counters are process
+// wide, so with several days running at once a peak would include (and a
+// reset would clobber) the other days' usage.
+fn forget_peaks(mut report: DayReport) -> DayReport {
+    if let Some(u) = &mut report.parse {
+        u.peak_bytes = None;
+    }
+    if let Status::Solved(parts) = &mut report.status {
+        parts.iter_mut().for_each(|p| p.usage.peak_bytes = None);
+    }
+    report
+}
+
+/// Run the given days on `workers` threads. Reports are still handed to
+/// `report` in the order of `all`, each one as soon as all earlier days have finished.
+/// Heap peaks are only reported when a single worker runs.
+pub fn run_parallel<F: FnMut(&DayReport)>(all: &[Day], workers: usize, mut report: F) -> Vec<DayReport> {
+    let next = AtomicUsize::new(0);
+    let mut reports: Vec<Option<DayReport>> = vec![None; all.len()];
+    let workers = workers.clamp(1, all.len().max(1));
+    thread::scope(|s| {
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let next = &next;
+            s.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= all.len() {
+                    break;
+                }
+                let r = match workers {
+                    1 => run_day(&all[i]),
+                    _ => forget_peaks(run_day(&all[i])),
+                };
+                if tx.send((i, r)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+        // hold back finished days until everything before them is reported
+        let mut reported = 0;
+        for (i, r) in rx {
+            reports[i] = Some(r);
+            while let Some(Some(r)) = reports.get(reported) {
+                report(r);
+                reported += 1;
+            }
+        }
+    });
+    reports.into_iter().flatten().collect()
+}
@@ -0,0 +1,49 @@
+use aoc20::answers::{self, Answers, Verdict};
+use aoc20::days::Answer;
+use aoc20::runner::{DayReport, PartResult, Status, Usage};
+
+fn report(part1: Answer, part2: Answer) -> DayReport {
+    let parts = vec![
+        PartResult { part: 1, answer: Ok(part1), usage: Usage::default() },
+        PartResult { part: 2, answer: Ok(part2), usage: Usage::default() },
+    ];
+    DayReport { day: 23, input: String::from("data/day23.txt"), parse: None, status: Status::Solved(parts) }
+}
+
+#[test]
+fn answers_parse() {
+    let answers = Answers::parse("# comment\n[day23]\npart1 = \"45983627\"\npart2 = 111057672960\n").unwrap();
+    assert_eq!(answers.get(23, 1), Some("45983627"));
+    assert_eq!(answers.get(23, 2), Some("111057672960"));
+    assert_eq!(answers.get(1, 1), None);
+}
+
+#[test]
+fn answers_parse_errors() {
+    assert_eq!(Answers::parse("part1 = 5"), Err(String::from("line 1: answer outside of a `[dayN]` table")));
+    assert_eq!(Answers::parse("[day1]\nanswer = 5"), Err(String::from("line 2: expected a `partN` key")));
+    assert_eq!(Answers::parse("[tuesday]"), Err(String::from("line 1: expected a `[dayN]` table")));
+}
+
+#[test]
+fn answers_recorded_file() {
+    let answers = Answers::load("data/answers.toml").unwrap();
+    assert_eq!(answers.get(1, 1), Some("996996"));
+    let e = Answers::load("data/day1.txt").unwrap_err();
+    assert!(e.starts_with("data/day1.txt: line 1: "), "{}", e);
+}
+
+#[test]
+fn answers_verify() {
+    let answers = Answers::parse("[day23]\npart1 = \"45983627\"\npart2 = 5").unwrap();
+    let verdicts = answers::verify(&report(Answer::from("45983627"), Answer::Number(6)), &answers);
+    assert_eq!(verdicts, vec![
+        (1, Verdict::Match),
+        (2, Verdict::Mismatch { expected: String::from("5"), actual: String::from("6") }),
+    ]);
+    assert!(verdicts[1].1.is_failure());
+
+    let answers = Answers::parse("[day23]\npart1 = \"45983627\"").unwrap();
+    let verdicts = answers::verify(&report(Answer::from("45983627"), Answer::Unsolved), &answers);
+    assert_eq!(verdicts[1], (2, Verdict::Missing));
+}
@@ -0,0 +1,41 @@
+use std::env;
+use std::fs;
+use std::process::{Command, Output};
+
+fn aoc20(args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc20")).args(args).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+#[test]
+fn cli_day_banner() {
+    let stdout = String::from_utf8(aoc20(&["8", "data/day8example.txt"]).stdout).unwrap();
+    assert!(stdout.starts_with("Advent of Code 2020\nDay 8\n"));
+}
+
+#[test]
+fn cli_day8_asm_round_trip() {
+    let source = aoc20(&["disasm", "data/day8example.txt"]).stdout;
+    assert!(source.starts_with(b"    nop +0\nL1:\n"));
+    let path = env::temp_dir().join(format!("aoc20_cli_{}.s", std::process::id()));
+    fs::write(&path, &source).unwrap();
+    let raw = aoc20(&["asm", path.to_str().unwrap()]).stdout;
+    fs::remove_file(&path).unwrap();
+    let expected = fs::read_to_string("data/day8example.txt").unwrap();
+    assert_eq!(String::from_utf8(raw).unwrap().lines().collect::<Vec<&str>>(),
+               expected.lines().collect::<Vec<&str>>());
+}
+
+#[test]
+fn cli_day8_trace_replay() {
+    let dir = env::temp_dir();
+    let original = dir.join(format!("aoc20_cli_{}_original.trace", std::process::id()));
+    let patched = dir.join(format!("aoc20_cli_{}_patched.trace", std::process::id()));
+    fs::write(&original, aoc20(&["trace", "data/day8example.txt"]).stdout).unwrap();
+    fs::write(&patched, aoc20(&["trace", "data/day8example.txt", "7"]).stdout).unwrap();
+    let diff = aoc20(&["replay", original.to_str().unwrap(), patched.to_str().unwrap()]).stdout;
+    fs::remove_file(&original).unwrap();
+    fs::remove_file(&patched).unwrap();
+    assert!(String::from_utf8(diff).unwrap().starts_with("traces diverge at step 4\n"));
+}
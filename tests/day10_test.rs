@@ -1,4 +1,5 @@
 use std::fs;
+use aoc20::days::{Answer, Solution};
 use aoc20::days::day10;
 
 #[test]
@@ -8,7 +9,7 @@ fn day10_part1() {
 
     let ratings: Vec<usize> = contents.lines().map(|l| l.parse::<usize>().unwrap()).collect();
 
-    assert_eq!(day10::part1(&ratings), (7, 0, 5));
+    assert_eq!(day10::part1(&ratings), Some((7, 0, 5)));
 }
 
 #[test]
@@ -18,5 +19,40 @@ fn day10_part1_sample2() {
 
     let ratings: Vec<usize> = contents.lines().map(|l| l.parse::<usize>().unwrap()).collect();
 
-    assert_eq!(day10::part1(&ratings), (22, 0, 10));
-}
\ No newline at end of file
+    assert_eq!(day10::part1(&ratings), Some((22, 0, 10)));
+}
+
+#[test]
+fn day10_part1_gap() {
+    assert_eq!(day10::part1(&[1, 2, 7]), None);
+    assert_eq!(day10::part1(&[1, 1, 2]), None);
+    assert_eq!(day10::part1(&[]), Some((0, 0, 1)));
+    let day = day10::Day10::parse("1\n5").unwrap();
+    assert_eq!(day.part1(), Answer::Unsolved);
+}
+
+#[test]
+fn day10_part2() {
+    let contents = fs::read_to_string("data/day10example.txt")
+        .expect("Something went wrong reading the file");
+
+    let ratings: Vec<usize> = contents.lines().map(|l| l.parse::<usize>().unwrap()).collect();
+
+    assert_eq!(day10::part2(&ratings), 8);
+}
+
+#[test]
+fn day10_part2_sample2() {
+    let contents = fs::read_to_string("data/day10example2.txt")
+        .expect("Something went wrong reading the file");
+
+    let ratings: Vec<usize> = contents.lines().map(|l| l.parse::<usize>().unwrap()).collect();
+
+    assert_eq!(day10::part2(&ratings), 19208);
+}
+
+#[test]
+fn day10_part2_gap() {
+    assert_eq!(day10::part2(&[1, 2, 7]), 0);
+    assert_eq!(day10::part2(&[]), 1);
+}
@@ -6,7 +6,7 @@ fn get_layout(filename: &str, part: day11::Part) -> day11::SeatLayout {
     let contents = fs::read_to_string(filename)
         .expect("Something went wrong reading the file");
     
-    day11::SeatLayout::new(&contents, part)
+    day11::SeatLayout::new(&contents, part).unwrap()
 }
 
 #[test]
@@ -50,4 +50,10 @@ fn day11_part2_occupied2() {
 fn day11_part2_occupied3() {
     let layout = get_layout("data/day11example4.txt", day11::Part::Part1);
     assert_eq!(layout.adjacent_occupied_count(3, 3), 0);
-}
\ No newline at end of file
+}
+
+#[test]
+fn day11_parse_ragged() {
+    let e = day11::SeatLayout::new("L.L\nL", day11::Part::Part1).unwrap_err();
+    assert_eq!(e.to_string(), "line 2, column 2: expected a row of 3 cells like the first, found 1");
+}
@@ -1,14 +1,29 @@
+use aoc20::days::{Answer, Solution};
 use aoc20::days::day12;
 
 #[test]
 fn day12_parse() {
-    let i = day12::Instruction::parse("F10");
+    let i = day12::Instruction::parse("F10").unwrap();
     assert_eq!(i, day12::Instruction::new('F', 10));
 }
 
 #[test]
 fn day12_parse_turn() {
-    let i = day12::Instruction::parse("R90");
+    let i = day12::Instruction::parse("R90").unwrap();
     assert_eq!(i, day12::Instruction::new('R', 90));
 }
 
+
+#[test]
+fn day12_parse_full_turn() {
+    assert_eq!(day12::Instruction::parse("L360").unwrap(), day12::Instruction::new('L', 360));
+    assert_eq!(day12::Instruction::parse("R360").unwrap(), day12::Instruction::new('R', 360));
+    assert!(day12::Instruction::parse("R450").is_err());
+
+    // a full turn leaves both the ship and the waypoint where they were
+    for turn in &["L360", "R360", "L0"] {
+        let day = day12::Day12::parse(&format!("{}\nF10", turn)).unwrap();
+        assert_eq!(day.part1(), Answer::Number(10));
+        assert_eq!(day.part2(), Answer::Number(110));
+    }
+}
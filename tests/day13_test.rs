@@ -1,27 +1,44 @@
+use aoc20::days::{Answer, Solution};
 use aoc20::days::day13;
 
 #[test]
 fn day13_example1() {
-    assert_eq!(3417, day13::calc_part2(&vec![17, 1, 13, 19]));
+    assert_eq!(Some(3417), day13::calc_part2(&[17, 1, 13, 19]));
 }
 
 #[test]
 fn day13_example2() {
-    assert_eq!(754018, day13::calc_part2(&vec![67, 7, 59, 61]));
+    assert_eq!(Some(754018), day13::calc_part2(&[67, 7, 59, 61]));
 }
 
 #[test]
 fn day13_example3() {
-    assert_eq!(779210, day13::calc_part2(&vec![67, 1, 7, 59, 61]));
+    assert_eq!(Some(779210), day13::calc_part2(&[67, 1, 7, 59, 61]));
 }
 
 #[test]
 fn day13_example4() {
-    assert_eq!(1261476, day13::calc_part2(&vec![67, 7, 1, 59, 61]));
+    assert_eq!(Some(1261476), day13::calc_part2(&[67, 7, 1, 59, 61]));
 }
 
 #[test]
 fn day13_example5() {
-    assert_eq!(1202161486, day13::calc_part2(&vec![1789, 37, 47, 1889]));
+    assert_eq!(Some(1202161486), day13::calc_part2(&[1789, 37, 47, 1889]));
 }
 
+#[test]
+fn day13_parse_errors() {
+    let e = day13::Schedule::parse("939\nx,x").unwrap_err();
+    assert_eq!(e.to_string(), "line 2: expected at least one bus ID");
+    let e = day13::Schedule::parse("939\n7,y").unwrap_err();
+    assert_eq!(e.to_string(), "line 2, column 3: expected a bus ID or `x`, found `y`");
+}
+
+#[test]
+fn day13_unsolvable() {
+    // 2 and 4 share a factor, so no timestamp fits both offsets
+    assert_eq!(day13::calc_part2(&[2, 4]), None);
+    let day = day13::Day13::parse("939\n2,4").unwrap();
+    assert_eq!(day.part1(), Answer::Number(2));
+    assert_eq!(day.part2(), Answer::Unsolved);
+}
@@ -2,48 +2,58 @@ use aoc20::days::day15;
 
 #[test]
 fn day15_test1() {
-    let g = day15::MemoryGame::new(&vec![0,3,6]);
-    assert_eq!(436, g.skip(2016).next().unwrap());
+    let mut g = day15::MemoryGame::new(&[0,3,6]);
+    assert_eq!(436, g.nth(2016).unwrap());
 }
 
 #[test]
 fn day15_test2() {
-    let g = day15::MemoryGame::new(&vec![1,3,2]);
-    assert_eq!(1, g.skip(2016).next().unwrap());
+    let mut g = day15::MemoryGame::new(&[1,3,2]);
+    assert_eq!(1, g.nth(2016).unwrap());
 }
 
 #[test]
 fn day15_test3() {
-    let g = day15::MemoryGame::new(&vec![2,1,3]);
-    assert_eq!(10, g.skip(2016).next().unwrap());
+    let mut g = day15::MemoryGame::new(&[2,1,3]);
+    assert_eq!(10, g.nth(2016).unwrap());
 }
 
 #[test]
 fn day15_test4() {
-    let g = day15::MemoryGame::new(&vec![1,2,3]);
-    assert_eq!(27, g.skip(2016).next().unwrap());
+    let mut g = day15::MemoryGame::new(&[1,2,3]);
+    assert_eq!(27, g.nth(2016).unwrap());
 }
 
 #[test]
 fn day15_test5() {
-    let g = day15::MemoryGame::new(&vec![2,3,1]);
-    assert_eq!(78, g.skip(2016).next().unwrap());
+    let mut g = day15::MemoryGame::new(&[2,3,1]);
+    assert_eq!(78, g.nth(2016).unwrap());
 }
 
 #[test]
 fn day15_test6() {
-    let g = day15::MemoryGame::new(&vec![3,2,1]);
-    assert_eq!(438, g.skip(2016).next().unwrap());
+    let mut g = day15::MemoryGame::new(&[3,2,1]);
+    assert_eq!(438, g.nth(2016).unwrap());
 }
 
 #[test]
 fn day15_test7() {
-    let g = day15::MemoryGame::new(&vec![3,1,2]);
-    assert_eq!(1836, g.skip(2016).next().unwrap());
+    let mut g = day15::MemoryGame::new(&[3,1,2]);
+    assert_eq!(1836, g.nth(2016).unwrap());
 }
 
-// #[test]
-// fn day15_part2_test1() {
-//     let g = day15::MemoryGame::new(&vec![0,3,6]);
-//     assert_eq!(175594, g.skip(30000000-4).next().unwrap());
-// }
\ No newline at end of file
+#[test]
+fn day15_part2_test1() {
+    let mut g = day15::MemoryGame::new(&[0,3,6]);
+    assert_eq!(175594, g.nth(30000000-4).unwrap());
+}
+
+#[test]
+fn day15_spoken() {
+    assert_eq!(day15::spoken(&[0,3,6], 1), Some(0));
+    assert_eq!(day15::spoken(&[0,3,6], 4), Some(0));
+    assert_eq!(day15::spoken(&[0,3,6], 10), Some(0));
+    assert_eq!(day15::spoken(&[0,3,6], 2020), Some(436));
+    assert_eq!(day15::spoken(&[3,1,2], 30000000), Some(362));
+    assert_eq!(day15::spoken(&[], 5), None);
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+use std::fs;
+use aoc20::days::{Answer, Solution};
+use aoc20::days::day16;
+
+fn read(filename: &str) -> (Vec<day16::Field>, Vec<Vec<usize>>) {
+    let contents = fs::read_to_string(filename)
+        .expect("Something went wrong reading the file");
+    let groups: Vec<&str> = contents.split("\n\n").collect();
+    let fields = groups[0].lines().map(day16::Field::parse).collect::<Result<_, _>>().unwrap();
+    let mut tickets = day16::parse_tickets(groups[1]).unwrap();
+    tickets.extend(day16::parse_tickets(groups[2]).unwrap());
+    (fields, tickets)
+}
+
+#[test]
+fn day16_parse_field() {
+    assert_eq!(day16::Field::parse("departure location: 36-363 or 377-962").unwrap(),
+        day16::Field::new(String::from("departure location"), (36, 363), (377, 962))
+    );
+    assert!(day16::Field::parse("class: 1-3").is_err());
+}
+
+#[test]
+fn day16_is_valid() {
+    let field = day16::Field::parse("class: 1-3 or 5-7").unwrap();
+    assert!(field.is_valid(1));
+    assert!(field.is_valid(7));
+    assert!(!field.is_valid(4));
+    assert!(!field.is_valid(8));
+}
+
+#[test]
+fn day16_part1() {
+    let (fields, tickets) = read("data/day16example.txt");
+    assert_eq!(day16::invalid_tickets(&fields, &tickets[1..]), vec![4, 55, 12]);
+}
+
+#[test]
+fn day16_identify_fields() {
+    let (fields, tickets) = read("data/day16example2.txt");
+    assert_eq!(day16::identify_fields(&fields, &tickets),
+        vec![Some(String::from("row")), Some(String::from("class")), Some(String::from("seat"))]
+    );
+}
+
+#[test]
+fn day16_example() {
+    let contents = fs::read_to_string("data/day16example.txt")
+        .expect("Something went wrong reading the file");
+    let day = day16::Day16::parse(&contents).unwrap();
+    assert_eq!(day.part1(), Answer::Number(71));
+
+    // no "departure" fields in the example, so the product is empty
+    let contents = fs::read_to_string("data/day16example2.txt")
+        .expect("Something went wrong reading the file");
+    let day = day16::Day16::parse(&contents).unwrap();
+    assert_eq!(day.part1(), Answer::Number(0));
+    assert_eq!(day.part2(), Answer::Number(1));
+}
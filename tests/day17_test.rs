@@ -0,0 +1,58 @@
+use std::fs;
+use aoc20::days::{Answer, Solution};
+use aoc20::days::day17::{self, Dimensions, PocketDimension, State};
+
+fn dimension(dimensions: Dimensions) -> PocketDimension {
+    let contents = fs::read_to_string("data/day17example.txt")
+        .expect("Something went wrong reading the file");
+    PocketDimension::new(&contents, dimensions).unwrap()
+}
+
+#[test]
+fn day17_parse() {
+    let p = dimension(Dimensions::Three);
+    assert_eq!(p.count(), 5);
+    assert_eq!(p.get(0, 0, -1, 0), State::Active);
+    assert_eq!(p.get(0, 0, -1, -1), State::Inactive);
+    assert!(PocketDimension::new(".#.\n..#", Dimensions::Three).is_err());
+    assert_eq!(PocketDimension::new(".#.\n..x\n###", Dimensions::Three).unwrap_err().to_string(),
+        "line 2, column 3: expected `#` or `.`, found `x`");
+}
+
+#[test]
+fn day17_rounds() {
+    let mut p = dimension(Dimensions::Three);
+    p.run_round();
+    assert_eq!(p.count(), 11);
+    p.run_round();
+    assert_eq!(p.count(), 21);
+    p.run_round();
+    assert_eq!(p.count(), 38);
+}
+
+#[test]
+fn day17_part1() {
+    let mut p = dimension(Dimensions::Three);
+    for _r in 0..6 {
+        p.run_round();
+    }
+    assert_eq!(p.count(), 112);
+}
+
+#[test]
+fn day17_part2() {
+    let mut p = dimension(Dimensions::Four);
+    for _r in 0..6 {
+        p.run_round();
+    }
+    assert_eq!(p.count(), 848);
+}
+
+#[test]
+fn day17_example() {
+    let contents = fs::read_to_string("data/day17example.txt")
+        .expect("Something went wrong reading the file");
+    let day = day17::Day17::parse(&contents).unwrap();
+    assert_eq!(day.part1(), Answer::Number(112));
+    assert_eq!(day.part2(), Answer::Number(848));
+}
@@ -0,0 +1,60 @@
+use std::fs;
+use aoc20::days::{Answer, Solution};
+use aoc20::days::day19;
+
+fn rules(filename: &str) -> day19::Rules {
+    let contents = fs::read_to_string(filename)
+        .expect("Something went wrong reading the file");
+    day19::Rules::parse(contents.split("\n\n").next().unwrap()).unwrap()
+}
+
+#[test]
+fn day19_parse_rule() {
+    assert_eq!(day19::Rule::parse("4: \"a\"").unwrap(), (4, day19::Rule::Char('a')));
+    assert_eq!(day19::Rule::parse("1: 2 3 | 3 2").unwrap(),
+        (1, day19::Rule::Alternatives(vec![vec![2, 3], vec![3, 2]]))
+    );
+    assert_eq!(day19::Rule::parse("8: 42").unwrap(), (8, day19::Rule::Alternatives(vec![vec![42]])));
+    assert!(day19::Rule::parse("1: 2 |").is_err());
+    assert!(day19::Rule::parse("x: \"a\"").is_err());
+}
+
+#[test]
+fn day19_undefined_rule() {
+    assert_eq!(day19::Rules::parse("0: 1 2\n1: \"a\"").unwrap_err().to_string(), "line 1: undefined rule 2");
+}
+
+#[test]
+fn day19_matches() {
+    let rules = rules("data/day19example.txt");
+    assert!(rules.matches("ababbb"));
+    assert!(rules.matches("abbbab"));
+    assert!(!rules.matches("bababa"));
+    assert!(!rules.matches("aaabbb"));
+    assert!(!rules.matches("aaaabbb"));
+}
+
+#[test]
+fn day19_loops() {
+    let rules = rules("data/day19example2.txt");
+    assert!(!rules.matches("babbbbaabbbbbabbbbbbaabaaabaaa"));
+    assert!(rules.with_loops().matches("babbbbaabbbbbabbbbbbaabaaabaaa"));
+}
+
+#[test]
+fn day19_example() {
+    let contents = fs::read_to_string("data/day19example.txt")
+        .expect("Something went wrong reading the file");
+    let day = day19::Day19::parse(&contents).unwrap();
+    assert_eq!(day.part1(), Answer::Number(2));
+    assert_eq!(day.part2(), Answer::Unsolved);
+}
+
+#[test]
+fn day19_example2() {
+    let contents = fs::read_to_string("data/day19example2.txt")
+        .expect("Something went wrong reading the file");
+    let day = day19::Day19::parse(&contents).unwrap();
+    assert_eq!(day.part1(), Answer::Number(3));
+    assert_eq!(day.part2(), Answer::Number(12));
+}
@@ -0,0 +1,84 @@
+use std::fs;
+use itertools::Itertools;
+use aoc20::days::{Answer, Solution};
+use aoc20::days::day1::{self, KSum, Output};
+
+static EXAMPLE: [i32; 6] = [1721, 979, 366, 299, 675, 1456];
+
+// every k-combination of indices adding up to target, the slow way
+fn brute_force(entries: &[i32], target: i64, k: usize) -> Vec<Vec<usize>> {
+    (0..entries.len()).combinations(k)
+                      .filter(|c| c.iter().map(|&i| entries[i] as i64).sum::<i64>() == target)
+                      .collect()
+}
+
+// small pseudo random entries with plenty of duplicates and negatives
+fn entries(seed: u64, len: usize) -> Vec<i32> {
+    let mut state = seed;
+    (0..len).map(|_| {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((state >> 33) % 21) as i32 - 10
+    }).collect()
+}
+
+#[test]
+fn day1_k_sum_example() {
+    assert_eq!(day1::k_sum(&EXAMPLE, 2020, 2), vec![vec![0, 3]]);
+    assert_eq!(day1::k_sum(&EXAMPLE, 2020, 3), vec![vec![1, 2, 4]]);
+    assert_eq!(day1::k_product(&EXAMPLE, 2020, 2), Some(514579));
+    assert_eq!(day1::k_product(&EXAMPLE, 2020, 3), Some(241861950));
+    assert_eq!(day1::k_product(&EXAMPLE, 1, 3), None);
+}
+
+#[test]
+fn day1_k_sum_output() {
+    assert_eq!(day1::k_sum_with(&EXAMPLE, 2020, 2, Output::Indices), vec![KSum::Indices(vec![0, 3])]);
+    assert_eq!(day1::k_sum_with(&EXAMPLE, 2020, 2, Output::Values), vec![KSum::Values(vec![1721, 299])]);
+    assert_eq!(day1::k_sum_with(&EXAMPLE, 2020, 2, Output::Sum), vec![KSum::Sum(2020)]);
+    assert_eq!(day1::k_sum_with(&EXAMPLE, 2020, 3, Output::Product), vec![KSum::Product(241861950)]);
+}
+
+#[test]
+fn day1_k_sum_duplicates() {
+    assert_eq!(day1::k_sum(&[5, 5, 5], 10, 2), vec![vec![0, 1], vec![0, 2], vec![1, 2]]);
+    assert_eq!(day1::k_sum(&[1, 1, 2, 2], 4, 3), vec![vec![0, 1, 2], vec![0, 1, 3]]);
+    assert_eq!(day1::k_sum(&[3, 3, 3, 3], 9, 3).len(), 4);
+}
+
+#[test]
+fn day1_k_sum_edge_cases() {
+    assert_eq!(day1::k_sum(&EXAMPLE, 0, 0), vec![Vec::<usize>::new()]);
+    assert_eq!(day1::k_sum(&EXAMPLE, 366, 1), vec![vec![2]]);
+    assert!(day1::k_sum(&[], 0, 2).is_empty());
+    assert!(day1::k_sum(&[2020], 2020, 2).is_empty());
+    assert!(day1::k_sum(&EXAMPLE, 2020, 7).is_empty());
+}
+
+#[test]
+fn day1_k_sum_matches_brute_force() {
+    for seed in 0..20 {
+        let entries = entries(seed, 14);
+        for k in 1..=5 {
+            for target in -6..=6 {
+                assert_eq!(day1::k_sum(&entries, target, k), brute_force(&entries, target, k),
+                    "seed {} k {} target {}", seed, k, target);
+            }
+        }
+    }
+}
+
+#[test]
+fn day1_find() {
+    assert_eq!(day1::find1(&EXAMPLE, 2020), Some(514579));
+    assert_eq!(day1::find2(&EXAMPLE, 2020), Some(241861950));
+    assert_eq!(day1::find(&EXAMPLE, 2020, 3), Some(241861950));
+}
+
+#[test]
+fn day1_example() {
+    let contents = fs::read_to_string("data/day1example.txt")
+        .expect("Something went wrong reading the file");
+    let day = day1::Day1::parse(&contents).unwrap();
+    assert_eq!(day.part1(), Answer::Number(514579));
+    assert_eq!(day.part2(), Answer::Number(241861950));
+}
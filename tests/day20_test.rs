@@ -0,0 +1,55 @@
+use std::fs;
+use aoc20::days::{Answer, Solution};
+use aoc20::days::day20::{self, Arrangement, Image, Tile};
+
+fn tiles() -> Vec<Tile> {
+    let contents = fs::read_to_string("data/day20example.txt")
+        .expect("Something went wrong reading the file");
+    contents.trim_end().split("\n\n").map(Tile::parse).collect::<Result<_, _>>().unwrap()
+}
+
+#[test]
+fn day20_parse_tile() {
+    assert_eq!(Tile::parse("Tile 7:\n#.\n.#").unwrap(),
+        Tile::new(7, Image::parse("#.\n.#"))
+    );
+    assert_eq!(Tile::parse("Tile x:\n#.\n.#").unwrap_err().line(), Some(1));
+    assert!(Tile::parse("Tile 7:\n#.\n.").is_err());
+}
+
+#[test]
+fn day20_orient() {
+    let image = Image::parse("#.\n..");
+    assert_eq!(image.orient(1, false), Image::parse(".#\n.."));
+    assert_eq!(image.orient(0, true), Image::parse(".#\n.."));
+    assert_eq!(image.orient(2, false), Image::parse("..\n.#"));
+}
+
+#[test]
+fn day20_part1() {
+    let arrangement = Arrangement::new(tiles());
+    let mut corners: Vec<usize> = arrangement.find_corners().iter().map(|t| t.id()).collect();
+    corners.sort_unstable();
+    assert_eq!(corners, vec![1171, 1951, 2971, 3079]);
+}
+
+#[test]
+fn day20_build_image() {
+    let mut arrangement = Arrangement::new(tiles());
+    arrangement.build_image();
+    let mut corners = arrangement.get_corners();
+    corners.sort_unstable();
+    assert_eq!(corners, vec![1171, 1951, 2971, 3079]);
+    let result = arrangement.get_result();
+    assert_eq!((result.height(), result.width()), (24, 24));
+    assert_eq!(result.count_char('#'), 303);
+}
+
+#[test]
+fn day20_example() {
+    let contents = fs::read_to_string("data/day20example.txt")
+        .expect("Something went wrong reading the file");
+    let day = day20::Day20::parse(&contents).unwrap();
+    assert_eq!(day.part1(), Answer::Number(20899048083289));
+    assert_eq!(day.part2(), Answer::Number(273));
+}
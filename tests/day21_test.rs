@@ -0,0 +1,46 @@
+use std::fs;
+use aoc20::days::{Answer, Solution};
+use aoc20::days::day21;
+
+fn foods() -> Vec<day21::Food> {
+    let contents = fs::read_to_string("data/day21example.txt")
+        .expect("Something went wrong reading the file");
+    contents.lines().map(day21::Food::parse).collect::<Result<_, _>>().unwrap()
+}
+
+#[test]
+fn day21_parse() {
+    assert_eq!(day21::Food::parse("sqjhc fvjkl (contains soy)").unwrap(),
+        day21::Food::new(vec![String::from("sqjhc"), String::from("fvjkl")], vec![String::from("soy")])
+    );
+    assert_eq!(day21::Food::parse("sqjhc fvjkl").unwrap(),
+        day21::Food::new(vec![String::from("sqjhc"), String::from("fvjkl")], vec![])
+    );
+    assert!(day21::Food::parse("sqjhc (contains soy").is_err());
+}
+
+#[test]
+fn day21_solve() {
+    let dangerous = day21::solve(&foods()).unwrap();
+    assert_eq!(dangerous["dairy"], "mxmxvkd");
+    assert_eq!(dangerous["fish"], "sqjhc");
+    assert_eq!(dangerous["soy"], "fvjkl");
+}
+
+#[test]
+fn day21_unresolved() {
+    let foods = vec![day21::Food::parse("a b (contains dairy)").unwrap()];
+    assert_eq!(day21::solve(&foods), None);
+}
+
+#[test]
+fn day21_example() {
+    assert_eq!(day21::safe_count(&foods()), 5);
+    assert_eq!(day21::canonical_dangerous(&foods()), Some(String::from("mxmxvkd,sqjhc,fvjkl")));
+
+    let contents = fs::read_to_string("data/day21example.txt")
+        .expect("Something went wrong reading the file");
+    let day = day21::Day21::parse(&contents).unwrap();
+    assert_eq!(day.part1(), Answer::Number(5));
+    assert_eq!(day.part2(), Answer::Text(String::from("mxmxvkd,sqjhc,fvjkl")));
+}
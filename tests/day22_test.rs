@@ -0,0 +1,50 @@
+use std::fs;
+use aoc20::days::{Answer, Solution};
+use aoc20::days::day22;
+use aoc20::days::day22::Winner;
+
+fn players(filename: &str) -> (day22::Player, day22::Player) {
+    let contents = fs::read_to_string(filename)
+        .expect("Something went wrong reading the file");
+    let mut groups = contents.trim_end().split("\n\n");
+    (day22::Player::parse(groups.next().unwrap()).unwrap(), day22::Player::parse(groups.next().unwrap()).unwrap())
+}
+
+#[test]
+fn day22_parse() {
+    assert_eq!(day22::Player::parse("Player 1:\n9\n2").unwrap(),
+        day22::Player::new(String::from("Player 1"), vec![9, 2])
+    );
+    assert_eq!(day22::Player::parse("Player 1:\n9\nx").unwrap_err().line(), Some(3));
+}
+
+#[test]
+fn day22_part1() {
+    let (player1, player2) = players("data/day22example.txt");
+    assert_eq!(day22::part1(player1, player2), 306);
+}
+
+#[test]
+fn day22_recursive_combat() {
+    let (mut player1, mut player2) = players("data/day22example.txt");
+    assert_eq!(day22::recursive_combat(&mut player1, &mut player2), Winner::Player2);
+    assert!(!player1.has_cards());
+    assert_eq!(player2.score(), 291);
+}
+
+#[test]
+fn day22_infinite_game() {
+    // without the repeated state check this game never ends
+    let (mut player1, mut player2) = players("data/day22example2.txt");
+    assert_eq!(day22::recursive_combat(&mut player1, &mut player2), Winner::Player1);
+    assert!(player2.has_cards());
+}
+
+#[test]
+fn day22_example() {
+    let contents = fs::read_to_string("data/day22example.txt")
+        .expect("Something went wrong reading the file");
+    let day = day22::Day22::parse(&contents).unwrap();
+    assert_eq!(day.part1(), Answer::Number(306));
+    assert_eq!(day.part2(), Answer::Number(291));
+}
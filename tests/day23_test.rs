@@ -0,0 +1,54 @@
+use std::fs;
+use aoc20::days::{Answer, Solution};
+use aoc20::days::day23::{self, CrabCups};
+
+fn example() -> CrabCups {
+    let contents = fs::read_to_string("data/day23example.txt")
+        .expect("Something went wrong reading the file");
+    CrabCups::parse(contents.trim()).unwrap()
+}
+
+#[test]
+fn day23_parse() {
+    assert_eq!(example().cups(), vec![3, 8, 9, 1, 2, 5, 4, 6, 7]);
+    assert_eq!(CrabCups::parse("25341").unwrap().len(), 5);
+    assert!(CrabCups::parse("3891254").is_err());
+    assert!(CrabCups::parse("1234").is_err());
+    assert_eq!(CrabCups::parse("38912x467").unwrap_err().column(), Some(6));
+}
+
+#[test]
+fn day23_moves() {
+    let mut game = example();
+    game.next();
+    assert_eq!(game.cups(), vec![2, 8, 9, 1, 5, 4, 6, 7, 3]);
+    game.next();
+    assert_eq!(game.cups(), vec![5, 4, 6, 7, 8, 9, 1, 3, 2]);
+    game.play(8);
+    assert_eq!(game.answer(), "92658374");
+}
+
+#[test]
+fn day23_with_total() {
+    let game = CrabCups::with_total(&[3, 8, 9, 1, 2, 5, 4, 6, 7], 12);
+    assert_eq!(game.cups(), vec![3, 8, 9, 1, 2, 5, 4, 6, 7, 10, 11, 12]);
+}
+
+#[test]
+fn day23_part1() {
+    assert_eq!(day23::part1(&example(), 10), "92658374");
+    assert_eq!(day23::part1(&example(), 100), "67384529");
+}
+
+#[test]
+fn day23_part2() {
+    assert_eq!(day23::part2(&example(), day23::PART2_CUPS, day23::PART2_MOVES), 149245887792);
+}
+
+#[test]
+fn day23_example() {
+    let contents = fs::read_to_string("data/day23example.txt")
+        .expect("Something went wrong reading the file");
+    let day = day23::Day23::parse(&contents).unwrap();
+    assert_eq!(day.part1(), Answer::Text(String::from("67384529")));
+}
@@ -0,0 +1,55 @@
+use std::fs;
+use aoc20::days::{Answer, Solution};
+use aoc20::days::day24::{self, Color, Direction, DirectionSequence, HexGrid};
+
+fn grid() -> HexGrid {
+    let contents = fs::read_to_string("data/day24example.txt")
+        .expect("Something went wrong reading the file");
+    let mut grid = HexGrid::new(150);
+    for line in contents.lines() {
+        grid.update(DirectionSequence::new(line).collect::<Result<_, _>>().unwrap());
+    }
+    grid
+}
+
+#[test]
+fn day24_direction_sequence() {
+    assert_eq!(DirectionSequence::new("esenee").collect::<Result<Vec<Direction>, _>>().unwrap(),
+        vec![Direction::East, Direction::SouthEast, Direction::NorthEast, Direction::East]
+    );
+    assert_eq!(DirectionSequence::new("esxe").collect::<Result<Vec<Direction>, _>>().unwrap_err().column(), Some(2));
+}
+
+#[test]
+fn day24_reference_tile() {
+    // nwwswee leads back to the reference tile
+    let mut grid = HexGrid::new(11);
+    grid.update(DirectionSequence::new("nwwswee").collect::<Result<_, _>>().unwrap());
+    assert_eq!(grid.get_tile((5, 4), Direction::East), Some(Color::Black));
+    assert_eq!(grid.count(Color::Black), 1);
+}
+
+#[test]
+fn day24_part1() {
+    assert_eq!(grid().count(Color::Black), 10);
+}
+
+#[test]
+fn day24_part2() {
+    let mut grid = grid();
+    assert_eq!(grid.next_day(), 15);
+    assert_eq!(grid.next_day(), 12);
+    for _d in 3..=10 {
+        grid.next_day();
+    }
+    assert_eq!(grid.count(Color::Black), 37);
+}
+
+#[test]
+fn day24_example() {
+    let contents = fs::read_to_string("data/day24example.txt")
+        .expect("Something went wrong reading the file");
+    let day = day24::Day24::parse(&contents).unwrap();
+    assert_eq!(day.part1(), Answer::Number(10));
+    assert_eq!(day.part2(), Answer::Number(2208));
+}
@@ -0,0 +1,38 @@
+use std::fs;
+use aoc20::days::{Answer, Solution};
+use aoc20::days::{day25, utils};
+
+#[test]
+fn day25_mod_pow() {
+    assert_eq!(utils::mod_pow(7, 8, day25::MODULUS), 5764801);
+    assert_eq!(utils::mod_pow(2, 10, 1000), 24);
+    assert_eq!(utils::mod_pow(5, 0, 13), 1);
+    assert_eq!(utils::mod_pow(5, 3, 1), 0);
+}
+
+#[test]
+fn day25_discrete_log() {
+    assert_eq!(utils::discrete_log(7, 5764801, day25::MODULUS), Some(8));
+    assert_eq!(utils::discrete_log(7, 17807724, day25::MODULUS), Some(11));
+    assert_eq!(utils::discrete_log(3, 1, 7), Some(0));
+    assert_eq!(utils::discrete_log(3, 13, 17), Some(4));
+    // 2 only generates 1, 2 and 4 mod 7
+    assert_eq!(utils::discrete_log(2, 3, 7), None);
+}
+
+#[test]
+fn day25_transform() {
+    assert_eq!(day25::transform(17807724, 8), 14897079);
+    assert_eq!(day25::transform(5764801, 11), 14897079);
+}
+
+#[test]
+fn day25_example() {
+    assert_eq!(day25::encryption_key(5764801, 17807724), Some(14897079));
+
+    let contents = fs::read_to_string("data/day25example.txt")
+        .expect("Something went wrong reading the file");
+    let day = day25::Day25::parse(&contents).unwrap();
+    assert_eq!(day.part1(), Answer::Number(14897079));
+    assert_eq!(day.part2(), Answer::Unsolved);
+}
@@ -4,12 +4,12 @@ use aoc20::days::day2::Policy;
 fn passwords() -> Vec<(Policy, String)> {
     let contents = fs::read_to_string("data/day2example.txt")
             .expect("Something went wrong reading the file");
-    contents.lines().map(|l| Policy::parse(l)).collect()
+    contents.lines().map(Policy::parse).collect::<Result<_, _>>().unwrap()
 }
 
 #[test]
 fn day2_parse() {
-    let (p, pwd) = Policy::parse("1-3 a: abcde");
+    let (p, pwd) = Policy::parse("1-3 a: abcde").unwrap();
     assert_eq!(p, Policy::new((1, 3), 'a'));
     assert_eq!(pwd, String::from("abcde"));
 }
@@ -1,5 +1,6 @@
 use std::fs;
 use aoc20::days::day3::{Geology};
+use aoc20::days::{day3, Solution};
 
 fn geology() -> Geology {
     let contents = fs::read_to_string("data/day3example.txt")
@@ -17,10 +18,10 @@ fn day3_parse() {
 #[test]
 fn day3_check_trees() {
     let geo = geology();
-    assert_eq!(geo.is_tree(10, 10), true);
-    assert_eq!(geo.is_tree(2, 1), true);
-    assert_eq!(geo.is_tree(0, 1), false);
-    assert_eq!(geo.is_tree(0, 13), true);
+    assert!(geo.is_tree(10, 10));
+    assert!(geo.is_tree(2, 1));
+    assert!(!geo.is_tree(0, 1));
+    assert!(geo.is_tree(0, 13));
 }
 
 #[test]
@@ -32,4 +33,12 @@ fn day3_hit_counts() {
     assert_eq!(geo.hit_trees(1, 5), 3);
     assert_eq!(geo.hit_trees(1, 7), 4);
     assert_eq!(geo.hit_trees(2, 1), 2);
-}
\ No newline at end of file
+}
+
+#[test]
+fn day3_parse_ragged() {
+    let e = Geology::parse("..#\n.\n#..").unwrap_err();
+    assert_eq!(e.to_string(), "line 2, column 2: expected a row of 3 cells like the first, found 1");
+    let e = day3::Day3::parse("..#\n#..#").unwrap_err();
+    assert_eq!(e.to_string(), "line 2, column 4: expected a row of 3 cells like the first, found 4");
+}
@@ -6,11 +6,11 @@ use aoc20::days::day5;
 
 #[test]
 fn day5_parse_id() {
-    let seat = day5::Seat::parse(&String::from("BFFFBBFRRR"));
+    let seat = day5::Seat::parse(&String::from("BFFFBBFRRR")).unwrap();
     assert_eq!(seat.id(), 567);
-    let seat = day5::Seat::parse(&String::from("FFFBBBFRRR"));
+    let seat = day5::Seat::parse(&String::from("FFFBBBFRRR")).unwrap();
     assert_eq!(seat.id(), 119);
-    let seat = day5::Seat::parse(&String::from("BBFFBBFRLL"));
+    let seat = day5::Seat::parse(&String::from("BBFFBBFRLL")).unwrap();
     assert_eq!(seat.id(), 820);
 }
 
@@ -17,7 +17,7 @@ fn day7_parse_bag() {
 #[test]
 fn day7_parse_line() {
     assert_eq!(
-        day7::Rule::parse("light lavender bags contain 1 dotted black bag, 1 wavy maroon bag, 5 pale white bags, 1 clear tan bag."),
+        day7::Rule::parse("light lavender bags contain 1 dotted black bag, 1 wavy maroon bag, 5 pale white bags, 1 clear tan bag.").unwrap(),
         day7::Rule::new(
             day7::Bag::parse("1 light lavender bags").unwrap(),
             vec![
@@ -29,7 +29,7 @@ fn day7_parse_line() {
         )
     );
     assert_eq!(
-        day7::Rule::parse("dotted black bags contain no other bags."),
+        day7::Rule::parse("dotted black bags contain no other bags.").unwrap(),
         day7::Rule::new(
             day7::Bag::parse("1 dotted black bags").unwrap(),
             vec![]
@@ -42,7 +42,7 @@ fn day7_part1() {
     let contents = fs::read_to_string("data/day7example.txt")
         .expect("Something went wrong reading the file");
 
-    let rules: Vec<day7::Rule> = contents.lines().map(|l| day7::Rule::parse(l)).collect();
+    let rules = contents.lines().map(day7::Rule::parse).collect::<Result<Vec<day7::Rule>, _>>().unwrap();
     assert_eq!(day7::part1("shiny gold", &rules), 4);
 }
 
@@ -51,7 +51,7 @@ fn day7_part2() {
     let contents = fs::read_to_string("data/day7example.txt")
         .expect("Something went wrong reading the file");
 
-    let rules: Vec<day7::Rule> = contents.lines().map(|l| day7::Rule::parse(l)).collect();
+    let rules = contents.lines().map(day7::Rule::parse).collect::<Result<Vec<day7::Rule>, _>>().unwrap();
     assert_eq!(day7::part2("shiny gold", &rules), 32);
 }
 
@@ -60,6 +60,6 @@ fn day7_part2_sample2() {
     let contents = fs::read_to_string("data/day7example2.txt")
         .expect("Something went wrong reading the file");
 
-    let rules: Vec<day7::Rule> = contents.lines().map(|l| day7::Rule::parse(l)).collect();
+    let rules = contents.lines().map(day7::Rule::parse).collect::<Result<Vec<day7::Rule>, _>>().unwrap();
     assert_eq!(day7::part2("shiny gold", &rules), 126);
 }
\ No newline at end of file
@@ -0,0 +1,60 @@
+use std::fs;
+use aoc20::days::day8::{self, Instruction, InstructionSet, Program};
+use aoc20::days::day8::analysis::{self, ControlFlowGraph, Repair};
+
+fn program(code: &str) -> Vec<Instruction> {
+    code.lines().map(Instruction::parse).collect::<Result<_, _>>().unwrap()
+}
+
+#[test]
+fn day8_control_flow_graph() {
+    let code = program(&fs::read_to_string("data/day8example.txt").unwrap());
+    let graph = ControlFlowGraph::new(&code);
+    assert_eq!(graph.end(), 9);
+    assert_eq!(graph.successors(0), &[1]);
+    assert_eq!(graph.successors(2), &[6]);
+    assert_eq!(graph.successors(7), &[3]);
+    assert_eq!(graph.successors(8), &[9]);
+    assert_eq!(graph.terminating(), vec![false, false, false, false, false, false, false, false, true]);
+
+    // jumps past the end terminate, jumps before the start go nowhere
+    let graph = ControlFlowGraph::new(&program("jmp +5\njmp -2\njz +1\nhlt +0"));
+    assert_eq!(graph.successors(0), &[4]);
+    assert!(graph.successors(1).is_empty());
+    assert_eq!(graph.successors(2), &[3, 3]);
+    assert_eq!(graph.terminating(), vec![true, false, false, false]);
+    assert_eq!(graph.always_terminating(), vec![true, false, false, false]);
+
+    // `jz` may reach the end or loop, depending on its register
+    let graph = ControlFlowGraph::new(&program("jz +2\njmp -1\nacc +1"));
+    assert_eq!(graph.terminating(), vec![true, true, true]);
+    assert_eq!(graph.always_terminating(), vec![false, false, true]);
+}
+
+#[test]
+fn day8_repair() {
+    let code = program(&fs::read_to_string("data/day8example.txt").unwrap());
+    assert_eq!(analysis::repair(&Program::new(code.clone())), Some(Repair { index: 7, acc: 8 }));
+
+    // flipping the first `jmp` lands on a jump back into the loop
+    let code = program("nop +0\njmp +3\njmp -2\nacc +5\nacc +2\njmp -5");
+    assert_eq!(analysis::repair(&Program::new(code)), Some(Repair { index: 5, acc: 2 }));
+
+    // flipping the first `jmp` reaches a `jz` that only terminates when acc is zero
+    let code = program("acc +1\njmp +2\njz +2\njmp -3");
+    let set = InstructionSet::extended();
+    assert_eq!(analysis::repair(&Program::with_instruction_set(code, set)), Some(Repair { index: 3, acc: 1 }));
+
+    // nothing to repair
+    assert_eq!(analysis::repair(&Program::new(program("acc +1\nnop +0"))), None);
+    assert_eq!(analysis::repair(&Program::new(program("jmp +0\njmp -1"))), None);
+}
+
+#[test]
+fn day8_repair_matches_brute_force() {
+    let contents = fs::read_to_string("data/day8.txt").unwrap();
+    let code = program(&contents);
+    assert_eq!(day8::part2(Program::new(code.clone())), day8::part2_brute_force(Program::new(code.clone())));
+    let set = InstructionSet::extended();
+    assert_eq!(analysis::repair(&Program::with_instruction_set(code, set)).map(|r| r.acc), Some(1160));
+}
@@ -0,0 +1,90 @@
+use std::fs;
+use aoc20::days::day8::{Instruction, InstructionSet, Opcode, Program};
+use aoc20::days::day8::asm;
+
+fn program(code: &str) -> Vec<Instruction> {
+    code.lines().map(Instruction::parse).collect::<Result<_, _>>().unwrap()
+}
+
+#[test]
+fn day8_assemble() {
+    let source = "\
+; count down from 3
+    acc +3
+loop:
+    jz done      # leave once acc is zero
+    acc -1
+    out +0
+    jmp loop
+done: hlt +0
+";
+    let code = asm::assemble(source).unwrap();
+    assert_eq!(code, program("acc +3\njz +4\nacc -1\nout +0\njmp -3\nhlt +0"));
+    let mut p = Program::with_instruction_set(code, InstructionSet::extended());
+    p.run();
+    assert_eq!(p.output(), &[2, 1, 0]);
+    assert!(p.halted());
+
+    // raw boot code is valid assembly
+    let contents = fs::read_to_string("data/day8example.txt").unwrap();
+    assert_eq!(asm::assemble(&contents).unwrap(), program(&contents));
+
+    // registers, and a label named like one
+    assert_eq!(asm::assemble("acc: jz r1 acc\nout r1 1").unwrap(), program("jz r1 +0\nout r1 +1"));
+    assert!(asm::assemble("acc r9 +1").is_err());
+
+    // a label past the last instruction and an unsigned offset
+    assert_eq!(asm::assemble("nop end\njmp 1\nend:").unwrap(),
+               vec![Instruction::new(Opcode::Nop, 2), Instruction::new(Opcode::Jmp, 1)]);
+}
+
+#[test]
+fn day8_assemble_errors() {
+    let e = asm::assemble("a: nop +0\njmp b\n").unwrap_err();
+    assert_eq!(e.to_string(), "line 2: undefined label `b`");
+    let e = asm::assemble("a: nop +0\na: jmp a\n").unwrap_err();
+    assert_eq!(e.to_string(), "line 2: duplicate label `a`");
+    let e = asm::assemble("top:\nacc top\n").unwrap_err();
+    assert_eq!(e.to_string(), "line 2: `acc` takes a number, not label `top`");
+    let e = asm::assemble("nop +0\n\nfoo +1").unwrap_err();
+    assert_eq!(e.line(), Some(3));
+    assert!(asm::assemble("jmp").is_err());
+}
+
+#[test]
+fn day8_disassemble() {
+    let code = program(&fs::read_to_string("data/day8example.txt").unwrap());
+    let source = asm::disassemble(&code);
+    assert_eq!(source, "    nop +0
+L1:
+    acc +1
+    jmp L3          ; +4 -> 6
+L2:
+    acc +3
+    jmp L1          ; -3 -> 1
+    acc -99
+L3:
+    acc +1
+    jmp L2          ; -4 -> 3
+    acc +6
+");
+    assert_eq!(asm::assemble(&source).unwrap(), code);
+
+    // jumps out of the program keep their offset, jumps to the end get a label
+    let code = program("jz +2\njmp -5\nacc +1");
+    assert_eq!(asm::disassemble(&code), "    jz L1           ; +2 -> 2\n    jmp -5\nL1:\n    acc +1\n");
+    let code = program("jz r2 +2\nacc r2 -1\nhlt +0");
+    assert_eq!(asm::disassemble(&code), "    jz r2 L1        ; +2 -> 2\n    acc r2 -1\nL1:\n    hlt +0\n");
+    assert_eq!(asm::assemble(&asm::disassemble(&code)).unwrap(), code);
+    let code = program("acc +1\njmp +1");
+    assert_eq!(asm::disassemble(&code), "    acc +1\n    jmp L1          ; +1 -> 2\nL1:\n");
+    assert_eq!(asm::assemble(&asm::disassemble(&code)).unwrap(), code);
+}
+
+#[test]
+fn day8_disassemble_round_trip() {
+    let code = program(&fs::read_to_string("data/day8.txt").unwrap());
+    let source = asm::disassemble(Program::new(code.clone()).code());
+    assert_eq!(asm::assemble(&source).unwrap(), code);
+    assert_eq!(asm::disassemble(&asm::assemble(&source).unwrap()), source);
+}
@@ -0,0 +1,119 @@
+use std::fs;
+use std::io::Cursor;
+use aoc20::days::day8::{Instruction, Program, Status};
+use aoc20::days::day8::debugger::{self, Breakpoint, Comparison, Debugger, Event};
+
+fn example() -> Debugger {
+    let contents = fs::read_to_string("data/day8example.txt").unwrap();
+    let code = contents.lines().map(Instruction::parse).collect::<Result<_, _>>().unwrap();
+    Debugger::new(Program::new(code))
+}
+
+#[test]
+fn day8_debugger_step() {
+    let mut debugger = example();
+    assert_eq!(debugger.step(3), Event::Stepped);
+    assert_eq!(debugger.program().pc(), 6);
+    assert_eq!(debugger.program().acc(), 1);
+    assert_eq!(debugger.step(10), Event::Stopped(Status::Looping));
+    assert_eq!(debugger.program().acc(), 5);
+    assert_eq!(debugger.backtrace(), vec![0, 1, 2, 6, 7, 3, 4]);
+}
+
+#[test]
+fn day8_debugger_breakpoints() {
+    let mut debugger = example();
+    let id = debugger.add_breakpoint(Breakpoint::Pc(3));
+    assert_eq!(debugger.resume(), Event::Break(id));
+    assert_eq!(debugger.program().pc(), 3);
+    assert_eq!(debugger.program().acc(), 2);
+    assert_eq!(debugger.resume(), Event::Stopped(Status::Looping));
+    assert!(debugger.remove_breakpoint(id));
+    assert!(!debugger.remove_breakpoint(id));
+    assert!(debugger.breakpoints().is_empty());
+
+    // a pending nth-execution breakpoint in the loop runs on past the loop stop
+    let mut debugger = example();
+    let id = debugger.add_breakpoint(Breakpoint::Nth { pc: 1, n: 3 });
+    assert_eq!(debugger.resume(), Event::Break(id));
+    assert_eq!(debugger.program().run_count(1), 2);
+    assert_eq!(debugger.program().acc(), 10);
+    assert_eq!(debugger.resume(), Event::Stopped(Status::Looping));
+    assert_eq!(debugger.program().pc(), 2);
+
+    // pc 0 never runs again, so the loop still stops the program
+    let mut debugger = example();
+    debugger.add_breakpoint(Breakpoint::Nth { pc: 0, n: 2 });
+    assert_eq!(debugger.resume(), Event::Stopped(Status::Looping));
+    assert_eq!(debugger.program().pc(), 1);
+    assert_eq!(debugger.program().acc(), 5);
+
+    // without loop detection the second pass over pc 1 reaches the breakpoint
+    let mut debugger = example();
+    debugger.stop_on_loop = false;
+    let id = debugger.add_breakpoint(Breakpoint::Nth { pc: 1, n: 2 });
+    assert_eq!(debugger.resume(), Event::Break(id));
+    assert_eq!(debugger.program().run_count(1), 1);
+    assert_eq!(debugger.program().acc(), 5);
+}
+
+#[test]
+fn day8_debugger_break_at_start() {
+    let mut debugger = example();
+    let id = debugger.add_breakpoint(Breakpoint::Pc(0));
+    assert_eq!(debugger.resume(), Event::Break(id));
+    assert_eq!(debugger.program().pc(), 0);
+    assert!(debugger.backtrace().is_empty());
+    // continuing runs past the breakpoint
+    assert_eq!(debugger.resume(), Event::Stopped(Status::Looping));
+
+    // stepping away from a breakpoint and back stops at it again
+    let mut debugger = example();
+    debugger.stop_on_loop = false;
+    let id = debugger.add_breakpoint(Breakpoint::Pc(1));
+    assert_eq!(debugger.resume(), Event::Break(id));
+    assert_eq!(debugger.step(1), Event::Stepped);
+    assert_eq!(debugger.resume(), Event::Break(id));
+    assert_eq!(debugger.program().run_count(1), 1);
+}
+
+#[test]
+fn day8_debugger_watchpoints() {
+    let mut debugger = example();
+    let id = debugger.add_breakpoint(Breakpoint::Acc(Comparison::Gt, 3));
+    assert_eq!(debugger.resume(), Event::Break(id));
+    assert_eq!(debugger.program().pc(), 4);
+    assert_eq!(debugger.program().acc(), 5);
+
+    let mut debugger = example();
+    let id = debugger.add_breakpoint(Breakpoint::AccChanged);
+    assert_eq!(debugger.step(5), Event::Break(id));
+    assert_eq!(debugger.program().pc(), 2);
+    assert_eq!(debugger.resume(), Event::Break(id));
+    assert_eq!(debugger.program().pc(), 7);
+    assert_eq!("<=".parse::<Comparison>().unwrap(), Comparison::Le);
+    assert!("=<".parse::<Comparison>().is_err());
+}
+
+#[test]
+fn day8_debugger_repl() {
+    let mut debugger = example();
+    let input = Cursor::new("break 4\ncontinue\nprint acc\nbacktrace\nfoo\np r1\np r9\nquit\nstep\n");
+    let mut output = Vec::new();
+    debugger::repl(&mut debugger, input, &mut output).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "\
+(dbg) breakpoint 1
+(dbg) breakpoint 1 (break at 4) at 4: jmp -3
+(dbg) 5
+(dbg) 3: acc +3
+7: jmp -4
+6: acc +1
+2: jmp +4
+1: acc +1
+0: nop +0
+(dbg) Unknown command: foo (try `help`)
+(dbg) 0
+(dbg) unknown register `r9`, expected `acc` or `r1` to `r7`
+(dbg) ");
+    assert_eq!(debugger.program().pc(), 4);
+}
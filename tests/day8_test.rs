@@ -1,14 +1,140 @@
-use aoc20::days::day8;
+use std::fs;
+use aoc20::days::{Answer, Solution};
+use aoc20::days::day8::{self, Flow, Instruction, InstructionSet, LoopDetection, Opcode, Program, Register, Status};
+use aoc20::days::day8::asm;
+
+fn program(code: &str) -> Vec<Instruction> {
+    code.lines().map(Instruction::parse).collect::<Result<_, _>>().unwrap()
+}
 
 #[test]
 fn day8_parse() {
-    assert_eq!(day8::Instruction::parse("nop +0"),
-        day8::Instruction::new(String::from("nop"), 0)
+    assert_eq!(day8::Instruction::parse("nop +0").unwrap(),
+        day8::Instruction::new(Opcode::Nop, 0)
     );
-    assert_eq!(day8::Instruction::parse("acc -99"),
-        day8::Instruction::new(String::from("acc"), -99)
+    assert_eq!(day8::Instruction::parse("acc -99").unwrap(),
+        day8::Instruction::new(Opcode::Acc, -99)
     );
-    assert_eq!(day8::Instruction::parse("jmp +4"),
-        day8::Instruction::new(String::from("jmp"), 4)
+    assert_eq!(day8::Instruction::parse("jmp +4").unwrap(),
+        day8::Instruction::new(Opcode::Jmp, 4)
     );
+    assert_eq!(day8::Instruction::parse("jz -2").unwrap(), day8::Instruction::new(Opcode::Jz, -2));
+    assert!(day8::Instruction::parse("foo +1").is_err());
+    assert!(day8::Instruction::parse("acc 1").is_err());
+    assert_eq!(day8::Instruction::parse("mul r3 -2").unwrap(),
+        day8::Instruction::with_register(Opcode::Mul, Register::new(3).unwrap(), -2)
+    );
+    assert_eq!(day8::Instruction::parse("acc acc +1").unwrap(), day8::Instruction::new(Opcode::Acc, 1));
+    assert!(day8::Instruction::parse("acc r8 +1").is_err());
+    assert!(day8::Instruction::parse("acc r0 +1").is_err());
+}
+
+#[test]
+fn day8_display() {
+    assert_eq!(day8::Instruction::new(Opcode::Nop, 0).to_string(), "nop +0");
+    assert_eq!(day8::Instruction::new(Opcode::Jmp, -3).to_string(), "jmp -3");
+    let r1 = "r1".parse::<Register>().unwrap();
+    assert_eq!(day8::Instruction::with_register(Opcode::Out, r1, 0).to_string(), "out r1 +0");
+    assert_eq!(day8::Instruction::with_register(Opcode::Acc, Register::ACC, 2).to_string(), "acc +2");
+}
+
+#[test]
+fn day8_run() {
+    let contents = fs::read_to_string("data/day8example.txt")
+        .expect("Something went wrong reading the file");
+    let mut p = Program::new(program(&contents));
+    assert_eq!(p.run(), 5);
+    assert!(p.looping());
+    assert!(!p.complete());
+    assert_eq!(day8::part2(Program::new(program(&contents))), Some(8));
+    assert_eq!(day8::part2(Program::new(program("jmp +0\njmp -1"))), None);
+    assert_eq!(day8::part2_brute_force(Program::new(program("jmp +0\njmp -1"))), None);
+}
+
+#[test]
+fn day8_example() {
+    let contents = fs::read_to_string("data/day8example.txt")
+        .expect("Something went wrong reading the file");
+    let day = day8::Day8::parse(&contents).unwrap();
+    assert_eq!(day.part1(), Answer::Number(5));
+    assert_eq!(day.part2(), Answer::Number(8));
+    assert_eq!(day8::Day8::parse("jmp +0\njmp -1").unwrap().part2(), Answer::Unsolved);
+    assert_eq!(day8::Day8::parse("nop +0\nmul +2").unwrap_err().to_string(), "line 2: unsupported opcode `mul`");
+    assert_eq!(day8::Day8::parse("acc r2 +1").unwrap_err().to_string(), "line 1: unsupported register `r2`");
+}
+
+#[test]
+fn day8_extended() {
+    let code = program("acc +3\nmul +4\nout +0\nacc -12\njz +2\nacc +100\nout +0\nhlt +0\nacc +1");
+    let mut p = Program::with_instruction_set(code.clone(), InstructionSet::extended());
+    assert_eq!(p.run(), 0);
+    assert!(p.halted());
+    assert_eq!(p.pc(), 7);
+    assert_eq!(p.output(), &[12, 0]);
+
+    // the standard set stops at the first extended instruction
+    let mut p = Program::new(code);
+    assert_eq!(p.run(), 3);
+    assert_eq!(p.fault(), Some("unsupported instruction `mul +4` at 1"));
+}
+
+#[test]
+fn day8_registers() {
+    // count r1 down from 3, adding it to acc each time
+    let code = program("acc r1 +3\njz r1 +5\nout r1 +0\nacc +1\nacc r1 -1\njmp -4\nout +0\nhlt +0");
+    let mut p = Program::with_instruction_set(code, InstructionSet::extended());
+    assert_eq!(p.run(), 3);
+    assert!(p.halted());
+    assert_eq!(p.output(), &[3, 2, 1, 3]);
+    assert_eq!(p.register("r1".parse().unwrap()), 0);
+
+    let swap = InstructionSet::standard().with(Opcode::Mul, |r, reg, a| {
+        let v = r[reg];
+        r[reg] = r.acc();
+        r[Register::ACC] = v * a;
+        Flow::Next
+    });
+    let mut p = Program::with_instruction_set(program("acc r2 +4\nacc +9\nmul r2 +10"), swap);
+    assert_eq!(p.run(), 40);
+    assert_eq!(p.register(Register::new(2).unwrap()), 9);
+}
+
+#[test]
+fn day8_counted_loop() {
+    let code = asm::assemble("acc r1 +3\ntop: jz r1 done\nacc r1 -1\nacc +5\njmp top\ndone: out +0\nhlt +0").unwrap();
+    let mut p = Program::with_instruction_set(code.clone(), InstructionSet::extended());
+    assert_eq!(p.run(), 15);
+    assert_eq!(p.status(), Status::Halted);
+    assert_eq!(p.output(), &[15]);
+    assert_eq!(p.run_count(1), 4);
+
+    // detecting loops by pc alone stops on the second pass
+    let mut p = Program::with_instruction_set(code, InstructionSet::extended());
+    p.set_loop_detection(LoopDetection::Pc);
+    assert_eq!(p.run(), 5);
+    assert_eq!(p.status(), Status::Looping);
+
+    // repeating the whole state is a loop
+    let code = program("acc r1 +1\njz r1 +2\njmp -1\nhlt +0");
+    let mut p = Program::with_instruction_set(code, InstructionSet::extended());
+    p.run();
+    assert_eq!(p.status(), Status::Looping);
+    assert_eq!(p.pc(), 1);
+    assert_eq!(InstructionSet::standard().loop_detection(), LoopDetection::Pc);
+    assert_eq!(InstructionSet::extended().loop_detection(), LoopDetection::State);
+}
+
+#[test]
+fn day8_custom_handler() {
+    let set = InstructionSet::standard().with(Opcode::Mul, |r, reg, a| { r[reg] = r[reg] * a + 1; Flow::Next });
+    let mut p = Program::with_instruction_set(program("acc +2\nmul +3"), set);
+    assert_eq!(p.run(), 7);
+    assert!(p.complete());
+}
+
+#[test]
+fn day8_jump_before_start() {
+    let mut p = Program::new(program("acc +1\njmp -2"));
+    p.run();
+    assert_eq!(p.fault(), Some("jump to -1 from 1"));
 }
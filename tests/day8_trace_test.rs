@@ -0,0 +1,99 @@
+use std::env;
+use std::fs;
+use aoc20::days::day8::{Instruction, InstructionSet, Program};
+use aoc20::days::day8::trace::{self, Trace, TraceEntry};
+
+fn example() -> Program {
+    let contents = fs::read_to_string("data/day8example.txt").unwrap();
+    Program::new(contents.lines().map(Instruction::parse).collect::<Result<_, _>>().unwrap())
+}
+
+#[test]
+fn day8_trace_record() {
+    let mut program = example();
+    assert!(program.trace().is_none());
+    program.record();
+    assert_eq!(program.run(), 5);
+    let trace = program.trace().unwrap();
+    assert_eq!(trace.entries().iter().map(|e| e.pc).collect::<Vec<usize>>(), vec![0, 1, 2, 6, 7, 3, 4]);
+    assert_eq!(trace.entries()[5], TraceEntry {
+        pc: 3,
+        instruction: Instruction::parse("acc +3").unwrap(),
+        before: 2,
+        after: 5,
+    });
+}
+
+#[test]
+fn day8_trace_round_trip() {
+    let mut program = example();
+    program.record();
+    program.run();
+    let trace = program.take_trace().unwrap();
+    assert!(program.trace().is_none());
+    assert_eq!(trace.entries()[0].to_string(), "   0  nop +0    acc 0 -> 0");
+    assert_eq!(Trace::parse(&trace.to_string()).unwrap(), trace);
+
+    let path = env::temp_dir().join(format!("aoc20_day8_{}.trace", std::process::id()));
+    trace.save(&path).unwrap();
+    assert_eq!(Trace::load(&path).unwrap(), trace);
+    fs::remove_file(&path).unwrap();
+
+    let entry = TraceEntry::parse("   3  mul r1 +2  r1 4 -> 8").unwrap();
+    assert_eq!(entry.instruction.to_string(), "mul r1 +2");
+    assert_eq!((entry.before, entry.after), (4, 8));
+    assert!(TraceEntry::parse("   3  mul r1 +2  acc 4 -> 8").is_err());
+
+    let e = Trace::parse("   0  nop +0    acc 0 -> 0\n   1  acc 1").unwrap_err();
+    assert_eq!(e.line(), Some(2));
+}
+
+#[test]
+fn day8_trace_diff() {
+    let program = example();
+    let (original, patched) = trace::trace_flip(&program, 7);
+    assert_eq!(original.diff(&original.clone()).map(|d| d.step), None);
+
+    let d = original.diff(&patched).unwrap();
+    assert_eq!(d.step, 4);
+    assert_eq!(d.original().unwrap().instruction.to_string(), "jmp -4");
+    assert_eq!(d.patched().unwrap().instruction.to_string(), "nop -4");
+    assert_eq!(d.to_string(), "\
+traces diverge at step 4
+     1  acc +1    acc 0 -> 1
+     2  jmp +4    acc 1 -> 1
+     6  acc +1    acc 1 -> 2
+-    7  jmp -4    acc 2 -> 2
+-    3  acc +3    acc 2 -> 5
+-    4  jmp -3    acc 5 -> 5
++    7  nop -4    acc 2 -> 2
++    8  acc +6    acc 2 -> 8
+");
+
+    // a prefix diverges where it ends
+    let mut short = example();
+    short.record();
+    short.step();
+    let d = short.trace().unwrap().diff(&original).unwrap();
+    assert_eq!(d.step, 1);
+    assert!(d.original().is_none());
+    assert!(d.to_string().contains("- (end of trace)"));
+}
+
+#[test]
+fn day8_trace_registers() {
+    let run = |source: &str| {
+        let mut program = Program::with_instruction_set(
+            source.lines().map(Instruction::parse).collect::<Result<_, _>>().unwrap(),
+            InstructionSet::extended());
+        program.record();
+        program.run();
+        program.take_trace().unwrap()
+    };
+    let original = run("acc r1 +2\nmul r1 +3\nout r1 +0");
+    let patched = run("acc r1 +2\nmul r1 +4\nout r1 +0");
+    assert_eq!(original.entries()[1].to_string(), "   1  mul r1 +3  r1 2 -> 6");
+    assert_eq!(Trace::parse(&original.to_string()).unwrap(), original);
+    // acc never changes, but r1 does
+    assert_eq!(original.diff(&patched).map(|d| d.step), Some(1));
+}
@@ -19,4 +19,44 @@ fn day9_part2() {
     let numbers: Vec<usize> = contents.lines().map(|l| l.parse::<usize>().unwrap()).collect();
 
     assert_eq!(day9::part2(&numbers, 127), Some((15, 47)));
-}
\ No newline at end of file
+}
+
+#[test]
+fn day9_window() {
+    let mut window = day9::Window::new(3);
+    [1, 2, 3].iter().for_each(|&n| window.push(n));
+    assert!(window.is_full());
+    assert!(window.is_valid(5));
+    assert!(!window.is_valid(6));
+    // the two numbers must be different
+    assert!(!window.is_valid(2));
+    window.push(3);
+    assert!(!window.is_valid(3));
+    assert!(!window.is_valid(6));
+    assert!(window.is_valid(5));
+}
+
+#[test]
+fn day9_window_bounded() {
+    // values that slide out of the window are forgotten
+    let mut window = day9::Window::new(25);
+    for n in 0..10_000 {
+        window.push(n);
+        assert!(window.distinct() <= 25);
+    }
+    assert_eq!(window.distinct(), 25);
+    assert!(window.is_valid(9_975 + 9_999));
+    assert!(!window.is_valid(1 + 9_999));
+    [7, 7, 7].iter().for_each(|&n| window.push(n));
+    assert_eq!(window.distinct(), 23);
+}
+
+#[test]
+fn day9_streaming() {
+    // every number after the first two must be a sum of the two before it
+    let fib = (0..).scan((1, 2), |s, _| { let n = s.0; *s = (s.1, s.0 + s.1); Some(n) });
+    let mut invalid = day9::invalid_numbers(fib.take(50).chain(vec![4, 7]), 2);
+    assert_eq!(invalid.next(), Some(4));
+    assert_eq!(invalid.next(), Some(7));
+    assert_eq!(invalid.next(), None);
+}
@@ -0,0 +1,35 @@
+use std::fs;
+use aoc20::days::{self, Answer};
+
+#[test]
+fn days_find() {
+    assert_eq!(days::find("day5").unwrap().number, 5);
+    assert_eq!(days::find("18").unwrap().name(), "day18");
+    assert!(days::find("day26").is_none());
+    assert!(days::find("bogus").is_none());
+}
+
+#[test]
+fn days_all_ordered() {
+    let numbers: Vec<u8> = days::all().iter().map(|d| d.number).collect();
+    let mut sorted = numbers.clone();
+    sorted.sort_unstable();
+    assert_eq!(numbers, sorted);
+}
+
+#[test]
+fn days_solve_example() {
+    let contents = fs::read_to_string("data/day1example.txt")
+        .expect("Something went wrong reading the file");
+
+    let solution = days::get(1).unwrap().parse(&contents).unwrap();
+    assert_eq!(solution.part1(), Answer::Number(514579));
+    assert_eq!(solution.part2(), Answer::Number(241861950));
+}
+
+#[test]
+fn answer_from_large_number() {
+    assert_eq!(Answer::from(i64::MAX as u64), Answer::Number(i64::MAX));
+    assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+    assert_eq!(Answer::from(-1isize), Answer::Number(-1));
+}
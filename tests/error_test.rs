@@ -0,0 +1,64 @@
+use std::fs;
+use aoc20::days::{self, Solution};
+use aoc20::days::{day16, day18};
+use aoc20::error::{self, AocError};
+use aoc20::runner;
+
+#[test]
+fn error_display() {
+    assert_eq!(AocError::parse("bad").to_string(), "bad");
+    assert_eq!(AocError::parse("bad").at_line(3).to_string(), "line 3: bad");
+    assert_eq!(AocError::parse("bad").at_column(2).at_line(3).to_string(), "line 3, column 2: bad");
+    // the innermost context wins
+    assert_eq!(AocError::parse("bad").at_line(3).at_line(7).line(), Some(3));
+}
+
+#[test]
+fn error_parse_lines() {
+    let r: Result<Vec<i32>, _> = error::parse_lines("1\n2\nx\n4", error::parse_number);
+    let e = r.unwrap_err();
+    assert_eq!(e.line(), Some(3));
+    assert_eq!(e.to_string(), "line 3: expected a number, found `x`");
+}
+
+#[test]
+fn error_parse_groups() {
+    let r: Result<Vec<Vec<i32>>, _> = error::parse_groups("1\n2\n\n3\nx", |g| {
+        error::parse_lines(g, error::parse_number)
+    });
+    assert_eq!(r.unwrap_err().line(), Some(5));
+}
+
+#[test]
+fn error_day16_field() {
+    let contents = fs::read_to_string("data/day16example.txt")
+        .expect("Something went wrong reading the file")
+        .replace("row: 6-11 or 33-44", "row: 6-11 and 33-44");
+    let e = day16::Day16::parse(&contents).unwrap_err();
+    assert_eq!(e.to_string(), "line 2: expected `name: a-b or c-d`");
+}
+
+#[test]
+fn error_day16_ticket() {
+    let contents = fs::read_to_string("data/day16example.txt")
+        .expect("Something went wrong reading the file")
+        .replace("55,2,20", "55,2");
+    let e = day16::Day16::parse(&contents).unwrap_err();
+    assert_eq!(e.line(), Some(11));
+}
+
+#[test]
+fn error_day18_columns() {
+    let e = day18::parse("1 + 2)", day18::Part::Part1).unwrap_err();
+    assert_eq!(e.column(), Some(6));
+    let e = day18::parse("1 + ?", day18::Part::Part1).unwrap_err();
+    assert_eq!(e.column(), Some(5));
+    assert!(day18::parse("(1 + 2", day18::Part::Part1).is_err());
+    assert!(day18::parse("1 +", day18::Part::Part1).is_err());
+}
+
+#[test]
+fn error_runner_reports_parse_error() {
+    let e = runner::run(days::get(2).unwrap(), "1-3 a: abcde\n1-3 b cdefg").unwrap_err();
+    assert!(e.starts_with("line 2:"));
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+use aoc20::days::Answer;
+use aoc20::output::{self, Format, Row};
+use aoc20::runner::{DayReport, Status, Usage};
+
+fn row(answer: Answer) -> Row {
+    Row {
+        day: 23,
+        part: Some(1),
+        status: "ok",
+        answer: Some(answer),
+        error: None,
+        elapsed_ns: Some(1500),
+        peak_bytes: None,
+        input: String::from("data/day23.txt"),
+    }
+}
+
+#[test]
+fn output_format_parse() {
+    assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+    assert_eq!("csv".parse::<Format>(), Ok(Format::Csv));
+    assert!("yaml".parse::<Format>().is_err());
+}
+
+#[test]
+fn output_json() {
+    assert_eq!(output::to_json(&row(Answer::Number(42))),
+        r#"{"day":23,"part":1,"status":"ok","answer":42,"error":null,"elapsed_ns":1500,"peak_bytes":null,"input":"data/day23.txt"}"#);
+    assert_eq!(output::to_json(&row(Answer::Text(String::from("45983627")))),
+        r#"{"day":23,"part":1,"status":"ok","answer":"45983627","error":null,"elapsed_ns":1500,"peak_bytes":null,"input":"data/day23.txt"}"#);
+}
+
+#[test]
+fn output_json_escape() {
+    assert_eq!(output::json_string("a \"b\"\n\\"), r#""a \"b\"\n\\""#);
+}
+
+#[test]
+fn output_csv() {
+    assert_eq!(output::to_csv(&row(Answer::Number(42))), "23,1,ok,42,,1500,,data/day23.txt");
+    let failed = Row { part: None, status: "failed", answer: None, error: Some(String::from("bad, \"input\"")), elapsed_ns: None, ..row(Answer::Unsolved) };
+    assert_eq!(output::to_csv(&failed), "23,,failed,,\"bad, \"\"input\"\"\",,,data/day23.txt");
+}
+
+#[test]
+fn output_parse_row() {
+    let report = DayReport {
+        day: 23,
+        input: String::from("data/day23.txt"),
+        parse: Some(Usage { elapsed: Duration::from_nanos(700), peak_bytes: Some(64) }),
+        status: Status::Solved(vec![]),
+    };
+    let rows = output::rows(&report);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(output::to_csv(&rows[0]), "23,,parsed,,,700,64,data/day23.txt");
+}
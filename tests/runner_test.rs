@@ -0,0 +1,80 @@
+use std::fs;
+use aoc20::days::{self, Answer};
+use aoc20::memory;
+use aoc20::runner;
+
+#[test]
+fn runner_run_example() {
+    let contents = fs::read_to_string("data/day1example.txt")
+        .expect("Something went wrong reading the file");
+
+    let (_, parts) = runner::run(days::get(1).unwrap(), &contents).unwrap();
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].answer, Ok(Answer::Number(514579)));
+    assert_eq!(parts[1].answer, Ok(Answer::Number(241861950)));
+}
+
+#[test]
+fn runner_catches_panic() {
+    assert!(runner::run(days::get(1).unwrap(), "not a number").is_err());
+}
+
+#[test]
+fn runner_failure() {
+    let report = runner::DayReport {
+        day: 1,
+        input: String::from("data/day1.txt"),
+        parse: None,
+        status: runner::Status::Failed(String::from("bad input")),
+    };
+    assert!(report.is_failure());
+    let report = runner::DayReport { status: runner::Status::MissingInput, ..report };
+    assert!(!report.is_failure());
+}
+
+#[test]
+fn runner_run_str() {
+    let report = runner::run_str(days::get(1).unwrap(), "<inline>", "1721\n979\n366\n299\n675\n1456");
+    assert_eq!(report.input, "<inline>");
+    match report.status {
+        runner::Status::Solved(parts) => assert_eq!(parts[0].answer, Ok(Answer::Number(514579))),
+        s => panic!("unexpected status {:?}", s),
+    }
+}
+
+#[test]
+fn runner_format_bytes() {
+    assert_eq!(runner::format_bytes(512), "512 B");
+    assert_eq!(runner::format_bytes(1536), "1.5 KiB");
+    assert_eq!(runner::format_bytes(3 * 1024 * 1024), "3.0 MiB");
+}
+
+#[test]
+fn runner_run_parallel_ordered() {
+    let selected = &days::all()[..8];
+    let mut seen = Vec::new();
+    let reports = runner::run_parallel(selected, 4, |r| seen.push(r.day));
+    let expected: Vec<u8> = selected.iter().map(|d| d.number).collect();
+    assert_eq!(seen, expected);
+    assert_eq!(reports.iter().map(|r| r.day).collect::<Vec<u8>>(), expected);
+}
+
+fn peaks(report: &runner::DayReport) -> Vec<Option<usize>> {
+    let mut peaks = vec![report.parse.and_then(|u| u.peak_bytes)];
+    if let runner::Status::Solved(parts) = &report.status {
+        peaks.extend(parts.iter().map(|p| p.usage.peak_bytes));
+    }
+    peaks
+}
+
+#[test]
+fn runner_run_parallel_peaks() {
+    // days share the allocation counters, so only a single worker reports peaks
+    let selected = &days::all()[..3];
+    for r in runner::run_parallel(selected, 2, |_r| ()) {
+        assert_eq!(peaks(&r), vec![None; 3]);
+    }
+    for r in runner::run_parallel(selected, 1, |_r| ()) {
+        assert!(peaks(&r).iter().all(|p| p.is_some() == memory::enabled()));
+    }
+}
@@ -0,0 +1,70 @@
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use aoc20::days::{day11, day17, day24};
+
+// Compare against the golden file tests/snapshots/<name>.txt. Run the tests
+// with UPDATE_SNAPSHOTS=1 to write the goldens after an intended change.
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = Path::new("tests/snapshots").join(format!("{}.txt", name));
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, actual).expect("Something went wrong writing the snapshot");
+        return;
+    }
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("Missing snapshot {}, run with UPDATE_SNAPSHOTS=1 to create it", path.display()));
+    if expected != actual {
+        let line = expected.lines().zip(actual.lines()).position(|(e, a)| e != a)
+                           .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+        panic!("Snapshot {} differs at line {}:\n  expected: {:?}\n  actual:   {:?}\nrun with UPDATE_SNAPSHOTS=1 if the change is intended",
+            path.display(), line + 1, expected.lines().nth(line), actual.lines().nth(line));
+    }
+}
+
+// Render the initial state and the state after each of `rounds` rounds
+fn render_rounds<T: Display, F: FnMut(&mut T)>(mut state: T, rounds: usize, mut round: F) -> String {
+    let mut output = format!("Initial state\n{}\n", state);
+    for r in 1..=rounds {
+        round(&mut state);
+        output.push_str(&format!("After round {}\n{}\n", r, state));
+    }
+    output
+}
+
+fn read(filename: &str) -> String {
+    fs::read_to_string(filename).expect("Something went wrong reading the file")
+}
+
+#[test]
+fn snapshot_day11_part1() {
+    let layout = day11::SeatLayout::new(&read("data/day11example.txt"), day11::Part::Part1).unwrap();
+    assert_snapshot("day11_part1", &render_rounds(layout, 6, |l| { l.run_round(); }));
+}
+
+#[test]
+fn snapshot_day11_part2() {
+    let layout = day11::SeatLayout::new(&read("data/day11example.txt"), day11::Part::Part2).unwrap();
+    assert_snapshot("day11_part2", &render_rounds(layout, 7, |l| { l.run_round(); }));
+}
+
+#[test]
+fn snapshot_day17_part1() {
+    let p = day17::PocketDimension::new(&read("data/day17example.txt"), day17::Dimensions::Three).unwrap();
+    assert_snapshot("day17_part1", &render_rounds(p, 3, |p| p.run_round()));
+}
+
+#[test]
+fn snapshot_day17_part2() {
+    let p = day17::PocketDimension::new(&read("data/day17example.txt"), day17::Dimensions::Four).unwrap();
+    assert_snapshot("day17_part2", &render_rounds(p, 2, |p| p.run_round()));
+}
+
+#[test]
+fn snapshot_day24() {
+    let mut grid = day24::HexGrid::new(150);
+    for line in read("data/day24example.txt").lines() {
+        grid.update(day24::DirectionSequence::new(line).collect::<Result<_, _>>().unwrap());
+    }
+    assert_snapshot("day24", &render_rounds(grid, 10, |g| { g.next_day(); }));
+}